@@ -1,10 +1,13 @@
+import { PublicKey, sendAndConfirmTransaction, Transaction } from "@solana/web3.js";
 import { execSync } from "child_process";
 import "dotenv/config";
 import { setTimeout } from "timers/promises";
-import { readCluster } from "../src";
+import { readCluster, SolanaClient } from "../src";
+import { AnchorReadonlyProvider } from "../src/client/AnchorReadonlyProvider";
+import { PriceAdapterContract } from "../src/price_adapter/PriceAdapterContract";
 import { buildCmd } from "./commands";
 import { readProgramAddress } from "./consts";
-import { readDeployDir } from "./utils";
+import { makeConnection, readDeployDir, readKeypair } from "./utils";
 
 const IS_VERIFIABLE: boolean = true; // Docker is needed for verifiable builds
 const IS_UPGRADE: boolean = true;
@@ -25,10 +28,37 @@ async function deploy(address: string, deployDir = readDeployDir(), cluster = re
   });
 }
 
+// The config account is created once, after the first deployment of the program;
+// the upgrades keep the existing one.
+async function initializeConfig(address: string) {
+  const connection = makeConnection();
+  const configAccount = PriceAdapterContract.getConfigAccount(new PublicKey(address));
+  if (await connection.getAccountInfo(configAccount)) {
+    console.log(`Config account ${configAccount.toBase58()} is already initialized`);
+    return;
+  }
+
+  const keypair = readKeypair();
+  const client = new SolanaClient(connection);
+  const provider = new AnchorReadonlyProvider(client, keypair.publicKey);
+  const contract = new PriceAdapterContract(address, provider, client);
+  const instruction = await contract.initializeConfigTx(keypair.publicKey);
+
+  const signature = await sendAndConfirmTransaction(
+    connection,
+    new Transaction().add(instruction),
+    [keypair]
+  );
+  console.log(`Config account ${configAccount.toBase58()} initialized in ${signature}`);
+}
+
 async function main() {
-  await deploy(readProgramAddress(readCluster()));
+  const address = readProgramAddress(readCluster());
+  await deploy(address);
 
   await setTimeout(15_000); // wait 15 seconds before continuing
+
+  await initializeConfig(address);
 }
 
 void main();
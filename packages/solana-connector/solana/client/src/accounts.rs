@@ -0,0 +1,87 @@
+use anchor_lang::{prelude::*, AccountDeserialize};
+use redstone_solana_price_adapter::{
+    error::PriceAdapterError,
+    state::{ConfigAccount, FeedIdBs, PayloadBuffer, PriceData, ScaledPriceData},
+    ID,
+};
+
+use crate::pda::price_address;
+
+/// Decodes the account data fetched off-chain, checking its discriminator.
+pub fn decode<T: AccountDeserialize>(data: &[u8]) -> Result<T> {
+    T::try_deserialize(&mut &data[..])
+}
+
+/// Decodes the `PriceData` with its `value`, `timestamp`, `decimals` and `write_slot_number`.
+pub fn decode_price_data(data: &[u8]) -> Result<PriceData> {
+    decode(data)
+}
+
+pub fn decode_config_account(data: &[u8]) -> Result<ConfigAccount> {
+    decode(data)
+}
+
+pub fn decode_scaled_price_data(data: &[u8]) -> Result<ScaledPriceData> {
+    decode(data)
+}
+
+pub fn decode_payload_buffer(data: &[u8]) -> Result<PayloadBuffer> {
+    decode(data)
+}
+
+/// Reads the `PriceData` of the `feed_id` directly from the account passed
+/// to another program, without the CPI to the price adapter.
+///
+/// Checks the address and the owner of the account, so a fake account can't be passed instead.
+pub fn read_price_data(price_account: &AccountInfo, feed_id: &FeedIdBs) -> Result<PriceData> {
+    require_keys_eq!(
+        price_account.key(),
+        price_address(feed_id),
+        PriceAdapterError::InvalidPriceAccount
+    );
+    require_keys_eq!(
+        *price_account.owner,
+        ID,
+        ErrorCode::AccountOwnedByWrongProgram
+    );
+
+    decode_price_data(&price_account.try_borrow_data()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pda::feed_id;
+
+    #[test]
+    fn price_data_roundtrip() {
+        let price_data = PriceData {
+            feed_id: feed_id("ETH"),
+            value: [7; 32],
+            timestamp: 1_700_000_000_000,
+            write_slot_number: 42,
+            decimals: 8,
+            ..Default::default()
+        };
+        let mut data = vec![];
+        price_data.try_serialize(&mut data).unwrap();
+
+        let decoded = decode_price_data(&data).unwrap();
+
+        assert_eq!(decoded.feed_id, price_data.feed_id);
+        assert_eq!(decoded.value, price_data.value);
+        assert_eq!(decoded.timestamp, price_data.timestamp);
+        assert_eq!(decoded.write_slot_number, 42);
+        assert_eq!(decoded.decimals, 8);
+    }
+
+    #[test]
+    fn decode_wrong_account() {
+        let mut data = vec![];
+        PayloadBuffer { data: vec![1, 2] }
+            .try_serialize(&mut data)
+            .unwrap();
+
+        assert!(decode_price_data(&data).is_err());
+    }
+}
@@ -0,0 +1,57 @@
+//! Reading the prices by the other programs via CPI to the price adapter.
+//!
+//! The `program` is the price adapter program account and the `price_account`
+//! is the account at [`crate::pda::price_address`] of the `feed_id`.
+
+use anchor_lang::prelude::*;
+use redstone_solana_price_adapter::{
+    cpi::{self, accounts::ReadPrice},
+    instructions::PriceAndTimestamp,
+    state::{FeedIdBs, ScaledPrice, ValueBs},
+};
+
+fn read_price_context<'info>(
+    program: AccountInfo<'info>,
+    price_account: AccountInfo<'info>,
+) -> CpiContext<'_, '_, '_, 'info, ReadPrice<'info>> {
+    CpiContext::new(program, ReadPrice { price_account })
+}
+
+pub fn price<'info>(
+    program: AccountInfo<'info>,
+    price_account: AccountInfo<'info>,
+    feed_id: FeedIdBs,
+) -> Result<ValueBs> {
+    Ok(cpi::price(read_price_context(program, price_account), feed_id)?.get())
+}
+
+pub fn price_and_timestamp<'info>(
+    program: AccountInfo<'info>,
+    price_account: AccountInfo<'info>,
+    feed_id: FeedIdBs,
+) -> Result<PriceAndTimestamp> {
+    Ok(cpi::price_and_timestamp(read_price_context(program, price_account), feed_id)?.get())
+}
+
+/// Fails when the price is older than `max_age_ms`.
+pub fn price_checked<'info>(
+    program: AccountInfo<'info>,
+    price_account: AccountInfo<'info>,
+    feed_id: FeedIdBs,
+    max_age_ms: u64,
+) -> Result<ValueBs> {
+    Ok(cpi::price_checked(
+        read_price_context(program, price_account),
+        feed_id,
+        max_age_ms,
+    )?
+    .get())
+}
+
+pub fn read_price_scaled<'info>(
+    program: AccountInfo<'info>,
+    price_account: AccountInfo<'info>,
+    feed_id: FeedIdBs,
+) -> Result<ScaledPrice> {
+    Ok(cpi::read_price_scaled(read_price_context(program, price_account), feed_id)?.get())
+}
@@ -0,0 +1,793 @@
+use anchor_lang::{
+    prelude::{AccountMeta, Pubkey},
+    solana_program::{instruction::Instruction, sysvar},
+    system_program, InstructionData, ToAccountMetas,
+};
+use redstone_solana_price_adapter::{
+    accounts, instruction,
+    state::{ConfigParams, DerivedPriceFormula, FeedIdBs, FeedMetadata},
+    ID,
+};
+
+#[cfg(feature = "event-cpi")]
+use crate::pda::event_authority_address;
+use crate::pda::{
+    config_address, payload_buffer_address, price_address, price_history_address,
+    scaled_price_address,
+};
+
+/// The optional accounts of the write instructions.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct WriteOptions {
+    /// Passes the `PriceHistory` accounts of the written feeds, which must be initialized
+    /// by [`init_price_history`] before.
+    pub with_history: bool,
+    /// Passes the `ScaledPriceData` accounts of the written feeds, refreshed with the written
+    /// prices, which must be created by [`update_scaled_price`] before.
+    pub with_scaled_price: bool,
+    /// Passes the instructions sysvar, so the signatures verified by the secp256k1
+    /// precompile instructions of the same transaction are not recovered again.
+    /// The precompile instructions must be added to the transaction by the caller.
+    pub precompile_verification: bool,
+}
+
+impl WriteOptions {
+    fn remaining_accounts(&self, feed_ids: &[FeedIdBs]) -> Vec<AccountMeta> {
+        let mut metas: Vec<_> = feed_ids
+            .iter()
+            .map(|feed_id| AccountMeta::new(price_address(feed_id), false))
+            .collect();
+
+        if self.with_history {
+            metas.extend(
+                feed_ids
+                    .iter()
+                    .map(|feed_id| AccountMeta::new(price_history_address(feed_id), false)),
+            );
+        }
+
+        if self.with_scaled_price {
+            metas.extend(
+                feed_ids
+                    .iter()
+                    .map(|feed_id| AccountMeta::new(scaled_price_address(feed_id), false)),
+            );
+        }
+
+        if self.precompile_verification {
+            metas.push(AccountMeta::new_readonly(sysvar::instructions::ID, false));
+        }
+
+        metas
+    }
+}
+
+fn make_instruction(
+    accounts: impl ToAccountMetas,
+    remaining_accounts: Vec<AccountMeta>,
+    data: impl InstructionData,
+) -> Instruction {
+    let mut metas = accounts.to_account_metas(None);
+    metas.extend(remaining_accounts);
+
+    Instruction {
+        program_id: ID,
+        accounts: metas,
+        data: data.data(),
+    }
+}
+
+pub fn initialize_config(payer: Pubkey) -> Instruction {
+    make_instruction(
+        accounts::InitializeConfig {
+            payer,
+            config_account: config_address(),
+            system_program: system_program::ID,
+        },
+        vec![],
+        instruction::InitializeConfig {},
+    )
+}
+
+fn update_config_accounts(admin: Pubkey) -> accounts::UpdateConfig {
+    accounts::UpdateConfig {
+        admin,
+        config_account: config_address(),
+    }
+}
+
+pub fn update_config(admin: Pubkey, params: ConfigParams) -> Instruction {
+    make_instruction(
+        update_config_accounts(admin),
+        vec![],
+        instruction::UpdateConfig { params },
+    )
+}
+
+pub fn set_feed_allowed(admin: Pubkey, feed_id: FeedIdBs, allowed: bool) -> Instruction {
+    make_instruction(
+        update_config_accounts(admin),
+        vec![],
+        instruction::SetFeedAllowed { feed_id, allowed },
+    )
+}
+
+pub fn set_pauser(admin: Pubkey, pauser: Pubkey) -> Instruction {
+    make_instruction(
+        update_config_accounts(admin),
+        vec![],
+        instruction::SetPauser { pauser },
+    )
+}
+
+pub fn set_updater_allowed(admin: Pubkey, updater: Pubkey, allowed: bool) -> Instruction {
+    make_instruction(
+        update_config_accounts(admin),
+        vec![],
+        instruction::SetUpdaterAllowed { updater, allowed },
+    )
+}
+
+pub fn set_updater_allow_list_enabled(admin: Pubkey, enabled: bool) -> Instruction {
+    make_instruction(
+        update_config_accounts(admin),
+        vec![],
+        instruction::SetUpdaterAllowListEnabled { enabled },
+    )
+}
+
+pub fn transfer_admin(admin: Pubkey, new_admin: Pubkey) -> Instruction {
+    make_instruction(
+        update_config_accounts(admin),
+        vec![],
+        instruction::TransferAdmin { new_admin },
+    )
+}
+
+pub fn accept_admin(pending_admin: Pubkey) -> Instruction {
+    make_instruction(
+        accounts::AcceptAdmin {
+            pending_admin,
+            config_account: config_address(),
+        },
+        vec![],
+        instruction::AcceptAdmin {},
+    )
+}
+
+/// Closes also the `PriceHistory` and the `ScaledPriceData` accounts of the feed
+/// when `with_history` and `with_scaled_price` are set, so they must exist then.
+pub fn close_price_account(
+    admin: Pubkey,
+    feed_id: FeedIdBs,
+    receiver: Pubkey,
+    with_history: bool,
+    with_scaled_price: bool,
+) -> Instruction {
+    make_instruction(
+        accounts::ClosePriceAccount {
+            admin,
+            config_account: config_address(),
+            price_account: price_address(&feed_id),
+            receiver,
+            price_history: with_history.then(|| price_history_address(&feed_id)),
+            scaled_price_account: with_scaled_price.then(|| scaled_price_address(&feed_id)),
+        },
+        vec![],
+        instruction::ClosePriceAccount { _feed_id: feed_id },
+    )
+}
+
+pub fn set_feed_metadata(admin: Pubkey, feed_id: FeedIdBs, metadata: FeedMetadata) -> Instruction {
+    make_instruction(
+        accounts::SetFeedMetadata {
+            admin,
+            config_account: config_address(),
+            price_account: price_address(&feed_id),
+            system_program: system_program::ID,
+        },
+        vec![],
+        instruction::SetFeedMetadata { feed_id, metadata },
+    )
+}
+
+pub fn realloc_price_account(payer: Pubkey, feed_id: FeedIdBs) -> Instruction {
+    make_instruction(
+        accounts::ReallocPriceAccount {
+            payer,
+            price_account: price_address(&feed_id),
+            system_program: system_program::ID,
+        },
+        vec![],
+        instruction::ReallocPriceAccount { _feed_id: feed_id },
+    )
+}
+
+pub fn set_paused(authority: Pubkey, paused: bool) -> Instruction {
+    make_instruction(
+        accounts::Pause {
+            authority,
+            config_account: config_address(),
+        },
+        vec![],
+        instruction::SetPaused { paused },
+    )
+}
+
+pub fn set_feed_paused(authority: Pubkey, feed_id: FeedIdBs, paused: bool) -> Instruction {
+    make_instruction(
+        accounts::PauseFeed {
+            authority,
+            config_account: config_address(),
+            price_account: price_address(&feed_id),
+        },
+        vec![],
+        instruction::SetFeedPaused {
+            _feed_id: feed_id,
+            paused,
+        },
+    )
+}
+
+pub fn write_price(
+    user: Pubkey,
+    feed_id: FeedIdBs,
+    payload: Vec<u8>,
+    options: WriteOptions,
+) -> Instruction {
+    let mut remaining_accounts = options.remaining_accounts(&[feed_id]);
+    // the price account is a named account of the instruction
+    remaining_accounts.remove(0);
+
+    make_instruction(
+        accounts::WritePrice {
+            user,
+            price_account: price_address(&feed_id),
+            config_account: config_address(),
+            system_program: system_program::ID,
+            #[cfg(feature = "event-cpi")]
+            event_authority: event_authority_address(),
+            #[cfg(feature = "event-cpi")]
+            program: ID,
+        },
+        remaining_accounts,
+        instruction::WritePrice { feed_id, payload },
+    )
+}
+
+pub fn write_prices(
+    user: Pubkey,
+    feed_ids: Vec<FeedIdBs>,
+    payload: Vec<u8>,
+    options: WriteOptions,
+) -> Instruction {
+    make_instruction(
+        accounts::WritePrices {
+            user,
+            config_account: config_address(),
+            system_program: system_program::ID,
+            #[cfg(feature = "event-cpi")]
+            event_authority: event_authority_address(),
+            #[cfg(feature = "event-cpi")]
+            program: ID,
+        },
+        options.remaining_accounts(&feed_ids),
+        instruction::WritePrices { feed_ids, payload },
+    )
+}
+
+pub fn init_payload_buffer(user: Pubkey, buffer_id: u64, capacity: u32) -> Instruction {
+    make_instruction(
+        accounts::InitPayloadBuffer {
+            user,
+            payload_buffer: payload_buffer_address(&user, buffer_id),
+            system_program: system_program::ID,
+        },
+        vec![],
+        instruction::InitPayloadBuffer {
+            _buffer_id: buffer_id,
+            capacity,
+        },
+    )
+}
+
+pub fn append_payload_chunk(
+    user: Pubkey,
+    buffer_id: u64,
+    offset: u32,
+    chunk: Vec<u8>,
+) -> Instruction {
+    make_instruction(
+        accounts::AppendPayloadChunk {
+            user,
+            payload_buffer: payload_buffer_address(&user, buffer_id),
+        },
+        vec![],
+        instruction::AppendPayloadChunk {
+            _buffer_id: buffer_id,
+            offset,
+            chunk,
+        },
+    )
+}
+
+/// Splits the `payload` into the [`append_payload_chunk`] instructions
+/// of at most `chunk_size` bytes each.
+pub fn append_payload_chunks(
+    user: Pubkey,
+    buffer_id: u64,
+    payload: &[u8],
+    chunk_size: usize,
+) -> Vec<Instruction> {
+    payload
+        .chunks(chunk_size)
+        .enumerate()
+        .map(|(index, chunk)| {
+            let offset = (index * chunk_size) as u32;
+            append_payload_chunk(user, buffer_id, offset, chunk.to_vec())
+        })
+        .collect()
+}
+
+pub fn close_payload_buffer(user: Pubkey, buffer_id: u64) -> Instruction {
+    make_instruction(
+        accounts::ClosePayloadBuffer {
+            user,
+            payload_buffer: payload_buffer_address(&user, buffer_id),
+        },
+        vec![],
+        instruction::ClosePayloadBuffer {
+            _buffer_id: buffer_id,
+        },
+    )
+}
+
+/// The `payload_hash` is the SHA-256 of the whole payload written to the buffer.
+/// As the signatures aren't part of this instruction, the secp256k1 precompile
+/// can't verify them and [`WriteOptions::precompile_verification`] has no use here.
+pub fn write_prices_from_buffer(
+    user: Pubkey,
+    buffer_id: u64,
+    feed_ids: Vec<FeedIdBs>,
+    payload_hash: [u8; 32],
+    options: WriteOptions,
+) -> Instruction {
+    make_instruction(
+        accounts::WritePricesFromBuffer {
+            user,
+            config_account: config_address(),
+            payload_buffer: payload_buffer_address(&user, buffer_id),
+            system_program: system_program::ID,
+            #[cfg(feature = "event-cpi")]
+            event_authority: event_authority_address(),
+            #[cfg(feature = "event-cpi")]
+            program: ID,
+        },
+        options.remaining_accounts(&feed_ids),
+        instruction::WritePricesFromBuffer {
+            _buffer_id: buffer_id,
+            feed_ids,
+            payload_hash,
+        },
+    )
+}
+
+pub fn init_price_history(user: Pubkey, feed_id: FeedIdBs) -> Instruction {
+    make_instruction(
+        accounts::InitPriceHistory {
+            user,
+            price_history: price_history_address(&feed_id),
+            system_program: system_program::ID,
+        },
+        vec![],
+        instruction::InitPriceHistory { feed_id },
+    )
+}
+
+pub fn update_scaled_price(user: Pubkey, feed_id: FeedIdBs) -> Instruction {
+    make_instruction(
+        accounts::UpdateScaledPrice {
+            user,
+            price_account: price_address(&feed_id),
+            scaled_price_account: scaled_price_address(&feed_id),
+            system_program: system_program::ID,
+        },
+        vec![],
+        instruction::UpdateScaledPrice { _feed_id: feed_id },
+    )
+}
+
+fn read_price_accounts(feed_id: &FeedIdBs) -> accounts::ReadPrice {
+    accounts::ReadPrice {
+        price_account: price_address(feed_id),
+    }
+}
+
+pub fn price_and_timestamp(feed_id: FeedIdBs) -> Instruction {
+    make_instruction(
+        read_price_accounts(&feed_id),
+        vec![],
+        instruction::PriceAndTimestamp { _feed_id: feed_id },
+    )
+}
+
+pub fn price(feed_id: FeedIdBs) -> Instruction {
+    make_instruction(
+        read_price_accounts(&feed_id),
+        vec![],
+        instruction::Price { _feed_id: feed_id },
+    )
+}
+
+pub fn timestamp(feed_id: FeedIdBs) -> Instruction {
+    make_instruction(
+        read_price_accounts(&feed_id),
+        vec![],
+        instruction::Timestamp { _feed_id: feed_id },
+    )
+}
+
+pub fn price_checked(feed_id: FeedIdBs, max_age_ms: u64) -> Instruction {
+    make_instruction(
+        read_price_accounts(&feed_id),
+        vec![],
+        instruction::PriceChecked {
+            _feed_id: feed_id,
+            max_age_ms,
+        },
+    )
+}
+
+pub fn read_price_scaled(feed_id: FeedIdBs) -> Instruction {
+    make_instruction(
+        read_price_accounts(&feed_id),
+        vec![],
+        instruction::ReadPriceScaled { _feed_id: feed_id },
+    )
+}
+
+fn read_price_history_accounts(feed_id: &FeedIdBs) -> accounts::ReadPriceHistory {
+    accounts::ReadPriceHistory {
+        price_history: price_history_address(feed_id),
+    }
+}
+
+pub fn latest_price_history(feed_id: FeedIdBs, count: u8) -> Instruction {
+    make_instruction(
+        read_price_history_accounts(&feed_id),
+        vec![],
+        instruction::LatestPriceHistory {
+            _feed_id: feed_id,
+            count,
+        },
+    )
+}
+
+pub fn price_twap(feed_id: FeedIdBs, window_ms: u64) -> Instruction {
+    make_instruction(
+        read_price_history_accounts(&feed_id),
+        vec![],
+        instruction::PriceTwap {
+            _feed_id: feed_id,
+            window_ms,
+        },
+    )
+}
+
+pub fn read_derived_price(formula: DerivedPriceFormula) -> Instruction {
+    let remaining_accounts = formula
+        .components
+        .iter()
+        .map(|component| AccountMeta::new_readonly(price_address(&component.feed_id), false))
+        .collect();
+
+    make_instruction(
+        accounts::ReadDerivedPrice {},
+        remaining_accounts,
+        instruction::ReadDerivedPrice { formula },
+    )
+}
+
+pub fn unique_signers_count() -> Instruction {
+    make_instruction(
+        accounts::ReadConfig {
+            config_account: config_address(),
+        },
+        vec![],
+        instruction::UniqueSignersCount {},
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use anchor_lang::{AnchorDeserialize, Discriminator};
+    use redstone_solana_price_adapter::state::{DerivedPriceComponent, DerivedPriceOperation};
+
+    use super::*;
+    use crate::pda::feed_id;
+
+    /// Decodes the data of the `instruction` back with the instruction struct of the program.
+    fn decode<T: AnchorDeserialize + Discriminator>(instruction: &Instruction) -> T {
+        assert_eq!(instruction.program_id, ID);
+
+        let (discriminator, data) = instruction.data.split_at(T::DISCRIMINATOR.len());
+        assert_eq!(discriminator, T::DISCRIMINATOR);
+
+        T::try_from_slice(data).unwrap()
+    }
+
+    #[test]
+    fn config_instructions_roundtrip() {
+        let admin = Pubkey::new_unique();
+        let other = Pubkey::new_unique();
+        let eth = feed_id("ETH");
+        let admin_accounts = [
+            AccountMeta::new_readonly(admin, true),
+            AccountMeta::new(config_address(), false),
+        ];
+
+        let instruction = update_config(
+            admin,
+            ConfigParams {
+                signer_count_threshold: 2,
+                signers: vec![[1; 20], [2; 20]],
+                trusted_updaters: vec![other],
+                max_timestamp_delay_ms: 3,
+                max_timestamp_ahead_ms: 4,
+                min_interval_between_updates_ms: 5,
+            },
+        );
+        assert_eq!(instruction.accounts, admin_accounts);
+        let params = decode::<instruction::UpdateConfig>(&instruction).params;
+        assert_eq!(params.signer_count_threshold, 2);
+        assert_eq!(params.signers, [[1; 20], [2; 20]]);
+        assert_eq!(params.trusted_updaters, [other]);
+        assert_eq!(params.max_timestamp_delay_ms, 3);
+        assert_eq!(params.max_timestamp_ahead_ms, 4);
+        assert_eq!(params.min_interval_between_updates_ms, 5);
+
+        let instruction = set_feed_allowed(admin, eth, true);
+        assert_eq!(instruction.accounts, admin_accounts);
+        let decoded = decode::<instruction::SetFeedAllowed>(&instruction);
+        assert_eq!(decoded.feed_id, eth);
+        assert!(decoded.allowed);
+
+        let decoded =
+            decode::<instruction::SetUpdaterAllowed>(&set_updater_allowed(admin, other, false));
+        assert_eq!(decoded.updater, other);
+        assert!(!decoded.allowed);
+
+        let decoded = decode::<instruction::SetUpdaterAllowListEnabled>(
+            &set_updater_allow_list_enabled(admin, true),
+        );
+        assert!(decoded.enabled);
+
+        let decoded = decode::<instruction::SetPauser>(&set_pauser(admin, other));
+        assert_eq!(decoded.pauser, other);
+
+        let decoded = decode::<instruction::TransferAdmin>(&transfer_admin(admin, other));
+        assert_eq!(decoded.new_admin, other);
+
+        let instruction = accept_admin(other);
+        decode::<instruction::AcceptAdmin>(&instruction);
+        assert_eq!(
+            instruction.accounts,
+            [
+                AccountMeta::new_readonly(other, true),
+                AccountMeta::new(config_address(), false),
+            ]
+        );
+    }
+
+    #[test]
+    fn write_instructions_roundtrip() {
+        let user = Pubkey::new_unique();
+        let feed_ids = vec![feed_id("ETH"), feed_id("BTC")];
+
+        let decoded = decode::<instruction::WritePrice>(&write_price(
+            user,
+            feed_ids[0],
+            vec![1, 2, 3],
+            Default::default(),
+        ));
+        assert_eq!(decoded.feed_id, feed_ids[0]);
+        assert_eq!(decoded.payload, [1, 2, 3]);
+
+        let decoded = decode::<instruction::WritePrices>(&write_prices(
+            user,
+            feed_ids.clone(),
+            vec![4, 5],
+            Default::default(),
+        ));
+        assert_eq!(decoded.feed_ids, feed_ids);
+        assert_eq!(decoded.payload, [4, 5]);
+    }
+
+    #[test]
+    fn payload_buffer_instructions_roundtrip() {
+        let user = Pubkey::new_unique();
+        let buffer = AccountMeta::new(payload_buffer_address(&user, 7), false);
+
+        let instruction = init_payload_buffer(user, 7, 1_000);
+        assert_eq!(instruction.accounts[1], buffer);
+        let decoded = decode::<instruction::InitPayloadBuffer>(&instruction);
+        assert_eq!(decoded._buffer_id, 7);
+        assert_eq!(decoded.capacity, 1_000);
+
+        let instruction = append_payload_chunk(user, 7, 800, vec![9; 10]);
+        assert_eq!(instruction.accounts[1], buffer);
+        let decoded = decode::<instruction::AppendPayloadChunk>(&instruction);
+        assert_eq!(decoded._buffer_id, 7);
+        assert_eq!(decoded.offset, 800);
+        assert_eq!(decoded.chunk, [9; 10]);
+
+        let instruction =
+            write_prices_from_buffer(user, 7, vec![feed_id("ETH")], [3; 32], Default::default());
+        assert_eq!(instruction.accounts[2], buffer);
+        let decoded = decode::<instruction::WritePricesFromBuffer>(&instruction);
+        assert_eq!(decoded._buffer_id, 7);
+        assert_eq!(decoded.feed_ids, [feed_id("ETH")]);
+        assert_eq!(decoded.payload_hash, [3; 32]);
+
+        let instruction = close_payload_buffer(user, 7);
+        assert_eq!(instruction.accounts[1], buffer);
+        assert_eq!(
+            decode::<instruction::ClosePayloadBuffer>(&instruction)._buffer_id,
+            7
+        );
+    }
+
+    #[test]
+    fn read_instructions_roundtrip() {
+        let eth = feed_id("ETH");
+        let btc = feed_id("BTC");
+
+        let instruction = price_checked(eth, 60_000);
+        assert_eq!(
+            instruction.accounts,
+            [AccountMeta::new_readonly(price_address(&eth), false)]
+        );
+        let decoded = decode::<instruction::PriceChecked>(&instruction);
+        assert_eq!(decoded._feed_id, eth);
+        assert_eq!(decoded.max_age_ms, 60_000);
+
+        let instruction = latest_price_history(eth, 5);
+        assert_eq!(
+            instruction.accounts,
+            [AccountMeta::new_readonly(
+                price_history_address(&eth),
+                false
+            )]
+        );
+        assert_eq!(
+            decode::<instruction::LatestPriceHistory>(&instruction).count,
+            5
+        );
+
+        let decoded = decode::<instruction::PriceTwap>(&price_twap(eth, 30_000));
+        assert_eq!(decoded.window_ms, 30_000);
+
+        let formula = DerivedPriceFormula {
+            components: vec![
+                DerivedPriceComponent {
+                    feed_id: eth,
+                    operation: DerivedPriceOperation::Multiply,
+                },
+                DerivedPriceComponent {
+                    feed_id: btc,
+                    operation: DerivedPriceOperation::Divide,
+                },
+            ],
+            decimals: 18,
+        };
+        let instruction = read_derived_price(formula.clone());
+        assert_eq!(
+            instruction.accounts,
+            [
+                AccountMeta::new_readonly(price_address(&eth), false),
+                AccountMeta::new_readonly(price_address(&btc), false),
+            ]
+        );
+        assert_eq!(
+            decode::<instruction::ReadDerivedPrice>(&instruction).formula,
+            formula
+        );
+    }
+
+    #[test]
+    fn close_price_account_optional_accounts() {
+        let admin = Pubkey::new_unique();
+        let receiver = Pubkey::new_unique();
+        let eth = feed_id("ETH");
+
+        let instruction = close_price_account(admin, eth, receiver, true, false);
+        assert_eq!(
+            decode::<instruction::ClosePriceAccount>(&instruction)._feed_id,
+            eth
+        );
+        assert_eq!(
+            instruction.accounts[2..],
+            [
+                AccountMeta::new(price_address(&eth), false),
+                AccountMeta::new(receiver, false),
+                AccountMeta::new(price_history_address(&eth), false),
+                // the missing optional account is replaced by the program id
+                AccountMeta::new_readonly(ID, false),
+            ]
+        );
+    }
+
+    #[test]
+    fn write_price_instruction() {
+        let user = Pubkey::new_unique();
+        let eth = feed_id("ETH");
+        let instruction = write_price(
+            user,
+            eth,
+            vec![1, 2, 3],
+            WriteOptions {
+                with_history: true,
+                with_scaled_price: true,
+                precompile_verification: true,
+            },
+        );
+
+        assert_eq!(instruction.program_id, ID);
+        assert_eq!(
+            instruction.data[..8],
+            [16, 186, 120, 224, 118, 178, 161, 152]
+        );
+        assert_eq!(instruction.accounts[0], AccountMeta::new(user, true));
+        assert_eq!(instruction.accounts[1].pubkey, price_address(&eth));
+
+        let remaining_accounts = &instruction.accounts[instruction.accounts.len() - 3..];
+        assert_eq!(
+            remaining_accounts,
+            [
+                AccountMeta::new(price_history_address(&eth), false),
+                AccountMeta::new(scaled_price_address(&eth), false),
+                AccountMeta::new_readonly(sysvar::instructions::ID, false),
+            ]
+        );
+    }
+
+    #[test]
+    fn write_prices_remaining_accounts() {
+        let feed_ids = vec![feed_id("ETH"), feed_id("BTC")];
+        let instruction = write_prices(
+            Pubkey::new_unique(),
+            feed_ids.clone(),
+            vec![],
+            WriteOptions {
+                with_history: true,
+                ..Default::default()
+            },
+        );
+
+        let remaining_accounts: Vec<_> = instruction.accounts[instruction.accounts.len() - 4..]
+            .iter()
+            .map(|meta| meta.pubkey)
+            .collect();
+        assert_eq!(
+            remaining_accounts,
+            [
+                price_address(&feed_ids[0]),
+                price_address(&feed_ids[1]),
+                price_history_address(&feed_ids[0]),
+                price_history_address(&feed_ids[1]),
+            ]
+        );
+    }
+
+    #[test]
+    fn append_payload_chunks_offsets() {
+        let user = Pubkey::new_unique();
+        let instructions = append_payload_chunks(user, 7, &[0; 2000], 800);
+
+        assert_eq!(instructions.len(), 3);
+        assert_eq!(
+            instructions[2],
+            append_payload_chunk(user, 7, 1600, vec![0; 400])
+        );
+    }
+}
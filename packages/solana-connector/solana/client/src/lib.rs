@@ -0,0 +1,11 @@
+//! Client of the RedStone Solana Price Adapter.
+//!
+//! Exposes the addresses of the program accounts, the builders of the program instructions,
+//! the decoding of the program accounts and the helpers for reading the prices via CPI.
+
+pub mod accounts;
+pub mod cpi;
+pub mod instructions;
+pub mod pda;
+
+pub use redstone_solana_price_adapter::{error, events, state, ID};
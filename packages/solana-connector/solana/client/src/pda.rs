@@ -0,0 +1,56 @@
+use anchor_lang::prelude::Pubkey;
+use redstone_solana_price_adapter::{
+    state::FeedIdBs,
+    util::{
+        make_config_seed, make_payload_buffer_seed, make_price_history_seed, make_price_seed,
+        make_scaled_price_seed,
+    },
+    ID,
+};
+
+const EVENT_AUTHORITY_SEED: &[u8] = b"__event_authority";
+
+/// Converts the feed name, e.g. `"ETH"`, to the zero-padded feed id used in the seeds.
+///
+/// # Panics
+///
+/// When the name is longer than 32 bytes.
+pub fn feed_id(name: &str) -> FeedIdBs {
+    let mut feed_id = FeedIdBs::default();
+    feed_id[..name.len()].copy_from_slice(name.as_bytes());
+
+    feed_id
+}
+
+pub fn price_address(feed_id: &FeedIdBs) -> Pubkey {
+    Pubkey::find_program_address(&[&make_price_seed(), feed_id], &ID).0
+}
+
+pub fn scaled_price_address(feed_id: &FeedIdBs) -> Pubkey {
+    Pubkey::find_program_address(&[&make_scaled_price_seed(), feed_id], &ID).0
+}
+
+pub fn price_history_address(feed_id: &FeedIdBs) -> Pubkey {
+    Pubkey::find_program_address(&[&make_price_history_seed(), feed_id], &ID).0
+}
+
+pub fn config_address() -> Pubkey {
+    Pubkey::find_program_address(&[&make_config_seed()], &ID).0
+}
+
+pub fn payload_buffer_address(user: &Pubkey, buffer_id: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            &make_payload_buffer_seed(),
+            user.as_ref(),
+            &buffer_id.to_le_bytes(),
+        ],
+        &ID,
+    )
+    .0
+}
+
+/// The signer of the self-CPI emitting the events when the `event-cpi` feature is enabled.
+pub fn event_authority_address() -> Pubkey {
+    Pubkey::find_program_address(&[EVENT_AUTHORITY_SEED], &ID).0
+}
@@ -0,0 +1,404 @@
+//! Cases the shared scenarios don't cover: accounts substituted by an attacker,
+//! the clock going backwards across slots and several writes within the same slot.
+//! The payloads are signed by [`test_signer`], set as the only signer of the config.
+
+use libsecp256k1::{Message, PublicKey, SecretKey};
+use redstone_testing::signer::ContractUpdateSigner as Actor;
+use sha3::{Digest, Keccak256};
+use solana_pubkey::Pubkey;
+
+use crate::environment::{
+    price_checked_instruction_data, scaled_price, write_price_instruction_data,
+    write_prices_instruction_data, Env, TestAccount,
+};
+
+const REDSTONE_MARKER: [u8; 9] = [0, 0, 2, 237, 87, 1, 30, 0, 0];
+const TIMESTAMP: u64 = 1_744_000_000_000;
+const MIN_INTERVAL_BETWEEN_UPDATES_MS: u64 = 40_000;
+const PRICE_UPDATE_REJECTED_EVENT_LOG: &str = "Program data: u9rr+m6b";
+
+fn test_signer() -> SecretKey {
+    SecretKey::parse(&[7; 32]).unwrap()
+}
+
+fn signer_address(secret_key: &SecretKey) -> [u8; 20] {
+    let public_key = PublicKey::from_secret_key(secret_key);
+
+    Keccak256::digest(&public_key.serialize()[1..])[12..]
+        .try_into()
+        .unwrap()
+}
+
+fn value(value: u128) -> [u8; 32] {
+    let mut bytes = [0u8; 32];
+    bytes[16..].copy_from_slice(&value.to_be_bytes());
+
+    bytes
+}
+
+/// A single data package with the `values` of the feeds, signed by [`test_signer`].
+fn payload(values: &[(&str, u128)], timestamp: u64) -> Vec<u8> {
+    let mut package = vec![];
+    for &(feed_id, feed_value) in values {
+        let mut feed_id = feed_id.as_bytes().to_vec();
+        feed_id.resize(32, 0);
+
+        package.append(&mut feed_id);
+        package.extend_from_slice(&value(feed_value));
+    }
+    package.extend_from_slice(&timestamp.to_be_bytes()[2..]);
+    package.extend_from_slice(&32u32.to_be_bytes());
+    package.extend_from_slice(&(values.len() as u32).to_be_bytes()[1..]);
+
+    let hash: [u8; 32] = Keccak256::digest(&package).into();
+    let (signature, recovery_id) = libsecp256k1::sign(&Message::parse(&hash), &test_signer());
+    package.extend_from_slice(&signature.serialize());
+    package.push(recovery_id.serialize() + 27);
+
+    package.extend_from_slice(&1u16.to_be_bytes());
+    // no unsigned metadata
+    package.extend_from_slice(&[0; 3]);
+    package.extend_from_slice(&REDSTONE_MARKER);
+
+    package
+}
+
+fn env() -> Env {
+    let mut env = Env::new();
+    let mut params = env.read_config_params();
+    params.signers = vec![signer_address(&test_signer())];
+    params.signer_count_threshold = 1;
+    params.min_interval_between_updates_ms = MIN_INTERVAL_BETWEEN_UPDATES_MS;
+    env.update_config(params, Actor::Trusted);
+
+    env.set_feed_allowed(b"ETH".to_vec(), true, Actor::Trusted);
+    env.set_feed_allowed(b"BTC".to_vec(), true, Actor::Trusted);
+    env.set_clock(1, TIMESTAMP);
+
+    env
+}
+
+#[test]
+#[should_panic(expected = "Error Code: AccountOwnedByWrongProgram")]
+fn read_price_account_owned_by_other_program() {
+    let mut env = env();
+    let mut account = env.price_data_account(b"ETH".to_vec(), value(100), TIMESTAMP);
+    account.owner = Pubkey::new_unique();
+    env.set_account(env.feed_address(b"ETH".to_vec()), account);
+
+    env.price_checked(b"ETH".to_vec(), u64::MAX);
+}
+
+#[test]
+#[should_panic(expected = "Error Code: AccountOwnedByWrongProgram")]
+fn write_prices_to_account_owned_by_other_program() {
+    let mut env = env();
+    let mut account = env.price_data_account(b"ETH".to_vec(), value(100), TIMESTAMP - 1_000);
+    account.owner = Pubkey::new_unique();
+    env.set_account(env.feed_address(b"ETH".to_vec()), account);
+
+    env.write_prices(
+        vec![b"ETH".to_vec()],
+        payload(&[("ETH", 200)], TIMESTAMP),
+        Actor::Trusted,
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error Code: ConstraintSeeds")]
+fn read_price_with_account_of_other_feed() {
+    let mut env = env();
+    env.set_price_data(b"ETH".to_vec(), value(100), TIMESTAMP);
+    env.set_price_data(b"BTC".to_vec(), value(200), TIMESTAMP);
+
+    env.simulate_instruction(
+        price_checked_instruction_data(b"ETH".to_vec(), u64::MAX),
+        env.account_metas(&[(TestAccount::Feed(b"BTC".to_vec()), false)]),
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error Code: ConstraintSeeds")]
+fn write_price_with_account_of_other_feed() {
+    let mut env = env();
+    env.set_price_data(b"BTC".to_vec(), value(200), TIMESTAMP - 1_000);
+
+    let accounts = env.account_metas(&[
+        (TestAccount::Actor(Actor::Trusted), true),
+        (TestAccount::Feed(b"BTC".to_vec()), true),
+        (TestAccount::Config, false),
+        (TestAccount::SystemProgram, false),
+    ]);
+    env.send_instruction(
+        write_price_instruction_data(payload(&[("ETH", 100)], TIMESTAMP), b"ETH".to_vec()),
+        accounts,
+        Actor::Trusted,
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error Code: InvalidPriceAccount")]
+fn write_prices_with_account_of_other_feed() {
+    let mut env = env();
+    env.set_price_data(b"BTC".to_vec(), value(200), TIMESTAMP - 1_000);
+
+    let accounts = env.account_metas(&[
+        (TestAccount::Actor(Actor::Trusted), true),
+        (TestAccount::Config, false),
+        (TestAccount::SystemProgram, false),
+        (TestAccount::Feed(b"BTC".to_vec()), true),
+    ]);
+    env.send_instruction(
+        write_prices_instruction_data(payload(&[("ETH", 100)], TIMESTAMP), vec![b"ETH".to_vec()]),
+        accounts,
+        Actor::Trusted,
+    );
+}
+
+#[test]
+fn trusted_write_with_clock_going_backwards() {
+    let mut env = env();
+    env.write_price(
+        b"ETH".to_vec(),
+        payload(&[("ETH", 100)], TIMESTAMP),
+        Actor::Trusted,
+    );
+
+    env.set_clock(2, TIMESTAMP - 10_000);
+    env.write_price(
+        b"ETH".to_vec(),
+        payload(&[("ETH", 200)], TIMESTAMP + 1_000),
+        Actor::Trusted,
+    );
+
+    let price_data = env.read_price_feed(b"ETH".to_vec());
+    assert_eq!(price_data.value, value(200));
+    assert_eq!(price_data.timestamp, TIMESTAMP + 1_000);
+    assert_eq!(price_data.write_timestamp, Some(TIMESTAMP - 10_000));
+    assert_eq!(price_data.write_slot_number, 2);
+}
+
+#[test]
+#[should_panic(expected = "rust-sdk. Error Number: 1102")]
+fn untrusted_write_with_clock_going_backwards() {
+    let mut env = env();
+    env.write_price(
+        b"ETH".to_vec(),
+        payload(&[("ETH", 100)], TIMESTAMP),
+        Actor::Untrusted,
+    );
+
+    env.set_clock(2, TIMESTAMP - 10_000);
+    env.write_price(
+        b"ETH".to_vec(),
+        payload(&[("ETH", 200)], TIMESTAMP + 1_000),
+        Actor::Untrusted,
+    );
+}
+
+#[test]
+fn trusted_writes_in_same_slot() {
+    let mut env = env();
+    env.write_price(
+        b"ETH".to_vec(),
+        payload(&[("ETH", 100)], TIMESTAMP - 1_000),
+        Actor::Trusted,
+    );
+    env.write_price(
+        b"ETH".to_vec(),
+        payload(&[("ETH", 200)], TIMESTAMP),
+        Actor::Trusted,
+    );
+
+    let price_data = env.read_price_feed(b"ETH".to_vec());
+    assert_eq!(price_data.value, value(200));
+    assert_eq!(price_data.timestamp, TIMESTAMP);
+    assert_eq!(price_data.write_slot_number, 1);
+}
+
+#[test]
+#[should_panic(expected = "rust-sdk. Error Number: 1102")]
+fn untrusted_writes_in_same_slot() {
+    let mut env = env();
+    env.write_price(
+        b"ETH".to_vec(),
+        payload(&[("ETH", 100)], TIMESTAMP - 1_000),
+        Actor::Untrusted,
+    );
+    env.write_price(
+        b"ETH".to_vec(),
+        payload(&[("ETH", 200)], TIMESTAMP),
+        Actor::Untrusted,
+    );
+}
+
+#[test]
+#[should_panic(expected = "rust-sdk. Error Number: 1101")]
+fn trusted_writes_of_same_package_in_same_slot() {
+    let mut env = env();
+    let payload = payload(&[("ETH", 100)], TIMESTAMP);
+
+    env.write_price(b"ETH".to_vec(), payload.clone(), Actor::Trusted);
+    env.write_price(b"ETH".to_vec(), payload, Actor::Trusted);
+}
+
+#[test]
+fn batch_and_single_writes_in_same_slot() {
+    let mut env = env();
+    env.write_prices(
+        vec![b"ETH".to_vec(), b"BTC".to_vec()],
+        payload(&[("ETH", 100), ("BTC", 300)], TIMESTAMP - 1_000),
+        Actor::Trusted,
+    );
+    env.write_price(
+        b"ETH".to_vec(),
+        payload(&[("ETH", 200)], TIMESTAMP),
+        Actor::Trusted,
+    );
+
+    let eth = env.read_price_feed(b"ETH".to_vec());
+    let btc = env.read_price_feed(b"BTC".to_vec());
+    assert_eq!((eth.value, eth.timestamp), (value(200), TIMESTAMP));
+    assert_eq!((btc.value, btc.timestamp), (value(300), TIMESTAMP - 1_000));
+    assert_eq!(eth.write_slot_number, btc.write_slot_number);
+}
+
+#[test]
+fn batch_write_skips_paused_feed() {
+    let mut env = env();
+    env.set_price_data(b"ETH".to_vec(), value(100), TIMESTAMP - 1_000);
+    env.set_feed_paused(b"ETH".to_vec(), true, Actor::Trusted);
+
+    env.write_prices(
+        vec![b"ETH".to_vec(), b"BTC".to_vec()],
+        payload(&[("ETH", 200), ("BTC", 300)], TIMESTAMP),
+        Actor::Trusted,
+    );
+
+    let eth = env.read_price_feed(b"ETH".to_vec());
+    let btc = env.read_price_feed(b"BTC".to_vec());
+    assert_eq!((eth.value, eth.timestamp), (value(100), TIMESTAMP - 1_000));
+    assert_eq!((btc.value, btc.timestamp), (value(300), TIMESTAMP));
+}
+
+#[test]
+fn batch_write_emits_price_update_rejected_event_for_skipped_feed() {
+    let mut env = env();
+    let logs = env.logs();
+    env.set_price_data(b"ETH".to_vec(), value(100), TIMESTAMP - 1_000);
+    env.set_feed_paused(b"ETH".to_vec(), true, Actor::Trusted);
+
+    env.write_prices(
+        vec![b"ETH".to_vec(), b"BTC".to_vec()],
+        payload(&[("ETH", 200), ("BTC", 300)], TIMESTAMP),
+        Actor::Trusted,
+    );
+
+    assert!(logs
+        .borrow()
+        .iter()
+        .any(|log| log.starts_with(PRICE_UPDATE_REJECTED_EVENT_LOG)));
+}
+
+#[test]
+#[should_panic(expected = "Error Code: DuplicateFeedId")]
+fn write_prices_with_duplicate_feed_ids() {
+    let mut env = env();
+
+    env.write_prices(
+        vec![b"ETH".to_vec(), b"ETH".to_vec()],
+        payload(&[("ETH", 100)], TIMESTAMP),
+        Actor::Trusted,
+    );
+}
+
+#[test]
+fn write_price_refreshes_scaled_price() {
+    let mut env = env();
+    env.set_price_data(b"ETH".to_vec(), value(100), TIMESTAMP - 1_000);
+    env.update_scaled_price(b"ETH".to_vec());
+
+    let accounts = env.account_metas(&[
+        (TestAccount::Actor(Actor::Trusted), true),
+        (TestAccount::Feed(b"ETH".to_vec()), true),
+        (TestAccount::Config, false),
+        (TestAccount::SystemProgram, false),
+        (TestAccount::ScaledPrice(b"ETH".to_vec()), true),
+    ]);
+    env.send_instruction(
+        write_price_instruction_data(payload(&[("ETH", 200)], TIMESTAMP), b"ETH".to_vec()),
+        accounts,
+        Actor::Trusted,
+    );
+
+    let scaled_price_data = env.read_scaled_price_feed(b"ETH".to_vec());
+    assert_eq!(scaled_price_data.price, 200);
+    assert_eq!(scaled_price_data.publish_time, (TIMESTAMP / 1_000) as i64);
+    assert_eq!(
+        scaled_price(&scaled_price_data),
+        env.read_price_scaled(b"ETH".to_vec())
+    );
+}
+
+#[test]
+fn write_prices_refreshes_scaled_prices() {
+    let mut env = env();
+    env.set_price_data(b"ETH".to_vec(), value(100), TIMESTAMP - 1_000);
+    env.set_price_data(b"BTC".to_vec(), value(300), TIMESTAMP - 1_000);
+    env.update_scaled_price(b"ETH".to_vec());
+    env.update_scaled_price(b"BTC".to_vec());
+
+    let accounts = env.account_metas(&[
+        (TestAccount::Actor(Actor::Trusted), true),
+        (TestAccount::Config, false),
+        (TestAccount::SystemProgram, false),
+        (TestAccount::Feed(b"ETH".to_vec()), true),
+        (TestAccount::Feed(b"BTC".to_vec()), true),
+        (TestAccount::ScaledPrice(b"ETH".to_vec()), true),
+        (TestAccount::ScaledPrice(b"BTC".to_vec()), true),
+    ]);
+    env.send_instruction(
+        write_prices_instruction_data(
+            payload(&[("ETH", 200), ("BTC", 400)], TIMESTAMP),
+            vec![b"ETH".to_vec(), b"BTC".to_vec()],
+        ),
+        accounts,
+        Actor::Trusted,
+    );
+
+    let eth = env.read_scaled_price_feed(b"ETH".to_vec());
+    let btc = env.read_scaled_price_feed(b"BTC".to_vec());
+    assert_eq!(eth.price, 200);
+    assert_eq!(btc.price, 400);
+    assert_eq!(btc.publish_time, (TIMESTAMP / 1_000) as i64);
+}
+
+#[test]
+#[should_panic(expected = "Error Code: InvalidScaledPriceAccount")]
+fn write_price_with_scaled_price_of_other_feed() {
+    let mut env = env();
+    env.set_price_data(b"BTC".to_vec(), value(300), TIMESTAMP - 1_000);
+    env.update_scaled_price(b"BTC".to_vec());
+
+    let accounts = env.account_metas(&[
+        (TestAccount::Actor(Actor::Trusted), true),
+        (TestAccount::Feed(b"ETH".to_vec()), true),
+        (TestAccount::Config, false),
+        (TestAccount::SystemProgram, false),
+        (TestAccount::ScaledPrice(b"BTC".to_vec()), true),
+    ]);
+    env.send_instruction(
+        write_price_instruction_data(payload(&[("ETH", 200)], TIMESTAMP), b"ETH".to_vec()),
+        accounts,
+        Actor::Trusted,
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error Code: PriceNotInitialized")]
+fn update_scaled_price_of_unwritten_feed() {
+    let mut env = env();
+    env.set_price_data(b"ETH".to_vec(), value(0), 0);
+
+    env.update_scaled_price(b"ETH".to_vec());
+}
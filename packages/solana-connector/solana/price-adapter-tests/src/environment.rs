@@ -1,7 +1,25 @@
-use core::{cell::RefCell, time::Duration};
+use core::{cell::RefCell, ops::Range, time::Duration};
+use std::rc::Rc;
 
-use litesvm::LiteSVM;
+use anchor_lang::{
+    prelude::Pubkey as ClientPubkey, solana_program::instruction::Instruction as ClientInstruction,
+    AccountSerialize, AnchorDeserialize, Discriminator,
+};
+use litesvm::{types::TransactionMetadata, LiteSVM};
+use redstone_solana_client::{
+    accounts,
+    instructions::{self, WriteOptions},
+    pda,
+    state::{
+        ConfigAccount, ConfigParams, DerivedPrice, DerivedPriceComponent, DerivedPriceFormula,
+        DerivedPriceOperation, FeedIdBs, FeedMetadata, HistoricalPrice, PriceData, PriceHistory,
+        PriceHistoryEntry, ScaledPrice, ScaledPriceData, PRICE_DATA_SPACE, PRICE_HISTORY_CAPACITY,
+    },
+};
 use redstone_testing::{self, env::PriceAdapterRunEnv, signer::ContractUpdateSigner as Actor};
+use sha2::Sha256;
+use sha3::{Digest, Keccak256};
+use solana_account::Account;
 use solana_clock::Clock;
 use solana_instruction::{account_meta::AccountMeta, Instruction};
 use solana_keypair::Keypair;
@@ -11,26 +29,25 @@ use solana_signature::Signature;
 use solana_signer::Signer;
 use solana_transaction::Transaction;
 
-const PROGRAM_ID: &str = "rds8J7VKqLQgzDr7vS59dkQga3B1BotgFy8F7LSLC74";
 const TRUSTED_UPDATER: &str = "f7a8654c99499d762eccafd584e8b16ab5119c162611f7c99f70d2d781fb3931";
 const SYSTEM_PROGRAM: &str = "11111111111111111111111111111111";
+const SECP256K1_PROGRAM: &str = "KeccakSecp256k11111111111111111111111111111";
 const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+pub const PAYLOAD_CHUNK_SIZE: usize = 800;
 
-use borsh::{BorshDeserialize, BorshSerialize};
-
-const WRITE_PRICE_DISCRIMINATOR: [u8; 8] = [16_u8, 186, 120, 224, 118, 178, 161, 152];
-const UNIQUE_SIGNER_THRESHOLD_DISCRIMINATOR: [u8; 8] = [101, 24, 86, 157, 116, 46, 226, 31];
+const PROGRAM_PATH: &str = "../target/deploy/redstone_solana_price_adapter.so";
 
-#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
-pub struct PriceData {
-    pub feed_id: [u8; 32],
-    pub value: [u8; 32],
-    pub timestamp: u64,
-    pub write_timestamp: Option<u64>,
+/// An account passed to the instructions built with [`Env::account_metas`].
+pub enum TestAccount {
+    Actor(Actor),
+    Feed(Vec<u8>),
+    PriceHistory(Vec<u8>),
+    ScaledPrice(Vec<u8>),
+    Config,
+    SystemProgram,
+    Address(Pubkey),
 }
 
-const PROGRAM_PATH: &str = "../target/deploy/redstone_solana_price_adapter.so";
-
 struct DummyKeypair {
     pub_key: Pubkey,
     counter: RefCell<u8>,
@@ -57,19 +74,45 @@ pub struct Env {
     signer: Keypair,
     trusted_signer: DummyKeypair,
     program_id: Pubkey,
+    batch_writes: bool,
+    buffered_writes: bool,
+    price_history: bool,
+    allow_feeds: bool,
+    feed_metadata: Option<FeedMetadata>,
+    pause_feeds: bool,
+    precompile_verification: bool,
+    logs: Rc<RefCell<Vec<String>>>,
+    compute_units: Rc<RefCell<Vec<u64>>>,
 }
 
-fn feed_seed(mut feed_id: Vec<u8>) -> Vec<u8> {
-    feed_id.resize(32, 0);
+/// The client is built with the solana crates of the program, newer than the ones of litesvm,
+/// so its keys and instructions are converted by their bytes.
+fn client_pubkey(pubkey: Pubkey) -> ClientPubkey {
+    ClientPubkey::new_from_array(pubkey.to_bytes())
+}
 
-    feed_id
+fn pubkey(pubkey: ClientPubkey) -> Pubkey {
+    Pubkey::new_from_array(pubkey.to_bytes())
 }
 
-fn price_seed() -> Vec<u8> {
-    let mut bytes = "price".as_bytes().to_vec();
-    bytes.resize(32, 0);
+fn instruction(instruction: ClientInstruction) -> Instruction {
+    Instruction {
+        program_id: pubkey(instruction.program_id),
+        accounts: instruction
+            .accounts
+            .into_iter()
+            .map(|meta| AccountMeta {
+                pubkey: pubkey(meta.pubkey),
+                is_signer: meta.is_signer,
+                is_writable: meta.is_writable,
+            })
+            .collect(),
+        data: instruction.data,
+    }
+}
 
-    bytes
+fn to_feed_id(feed_id: &[u8]) -> FeedIdBs {
+    pda::feed_id(core::str::from_utf8(feed_id).unwrap())
 }
 
 fn trusted_keypair() -> DummyKeypair {
@@ -79,14 +122,143 @@ fn trusted_keypair() -> DummyKeypair {
     }
 }
 
-fn write_price_instruction_data(mut payload: Vec<u8>, feed_id: Vec<u8>) -> Vec<u8> {
-    let mut data = WRITE_PRICE_DISCRIMINATOR.to_vec();
+/// The [`ScaledPrice`] mirrored by the `ScaledPriceData` account.
+pub fn scaled_price(scaled_price_data: &ScaledPriceData) -> ScaledPrice {
+    ScaledPrice {
+        price: scaled_price_data.price,
+        conf: scaled_price_data.conf,
+        expo: scaled_price_data.expo,
+        publish_time: scaled_price_data.publish_time,
+    }
+}
+
+/// Only the data of the instructions is used, so the user isn't relevant.
+pub fn price_checked_instruction_data(feed_id: Vec<u8>, max_age_ms: u64) -> Vec<u8> {
+    instructions::price_checked(to_feed_id(&feed_id), max_age_ms).data
+}
+
+pub fn write_price_instruction_data(payload: Vec<u8>, feed_id: Vec<u8>) -> Vec<u8> {
+    instructions::write_price(
+        ClientPubkey::default(),
+        to_feed_id(&feed_id),
+        payload,
+        Default::default(),
+    )
+    .data
+}
+
+pub fn write_prices_instruction_data(payload: Vec<u8>, feed_ids: Vec<Vec<u8>>) -> Vec<u8> {
+    instructions::write_prices(
+        ClientPubkey::default(),
+        feed_ids.iter().map(|feed_id| to_feed_id(feed_id)).collect(),
+        payload,
+        Default::default(),
+    )
+    .data
+}
+
+const REDSTONE_MARKER_BS: usize = 9;
+const UNSIGNED_METADATA_BYTE_SIZE_BS: usize = 3;
+const DATA_PACKAGES_COUNT_BS: usize = 2;
+const SIGNATURE_BS: usize = 65;
+const DATA_POINTS_COUNT_BS: usize = 3;
+const DATA_POINT_VALUE_BYTE_SIZE_BS: usize = 4;
+const TIMESTAMP_BS: usize = 6;
+const DATA_FEED_ID_BS: usize = 32;
+const SECP256K1_SIGNATURE_OFFSETS_BS: usize = 11;
+const ETH_ADDRESS_BS: usize = 20;
 
-    data.append(&mut feed_seed(feed_id));
-    data.extend_from_slice(&((payload.len()) as u32).to_le_bytes());
-    data.append(&mut payload);
+fn read_be_usize(bytes: &[u8]) -> usize {
+    bytes
+        .iter()
+        .fold(0, |acc, &byte| (acc << 8) | usize::from(byte))
+}
+
+/// Ranges of the signable message and the signature of each data package of the `payload`.
+fn data_packages(payload: &[u8]) -> Vec<(Range<usize>, Range<usize>)> {
+    let mut end = payload.len() - REDSTONE_MARKER_BS;
+    let metadata_size = read_be_usize(&payload[end - UNSIGNED_METADATA_BYTE_SIZE_BS..end]);
+    end -= UNSIGNED_METADATA_BYTE_SIZE_BS + metadata_size;
+    let package_count = read_be_usize(&payload[end - DATA_PACKAGES_COUNT_BS..end]);
+    end -= DATA_PACKAGES_COUNT_BS;
+
+    let mut packages = vec![];
+    for _ in 0..package_count {
+        let signature = end - SIGNATURE_BS..end;
+        end = signature.start;
+
+        let data_point_count = read_be_usize(&payload[end - DATA_POINTS_COUNT_BS..end]);
+        let value_size_end = end - DATA_POINTS_COUNT_BS;
+        let value_size =
+            read_be_usize(&payload[value_size_end - DATA_POINT_VALUE_BYTE_SIZE_BS..value_size_end]);
+        let start = value_size_end
+            - DATA_POINT_VALUE_BYTE_SIZE_BS
+            - TIMESTAMP_BS
+            - data_point_count * (DATA_FEED_ID_BS + value_size);
 
-    data
+        packages.push((start..end, signature));
+        end = start;
+    }
+
+    packages
+}
+
+fn signer_address(message: &[u8], signature: &[u8]) -> [u8; ETH_ADDRESS_BS] {
+    let hash: [u8; 32] = Keccak256::digest(message).into();
+    let public_key = libsecp256k1::recover(
+        &libsecp256k1::Message::parse(&hash),
+        &libsecp256k1::Signature::parse_standard_slice(&signature[..64]).unwrap(),
+        &libsecp256k1::RecoveryId::parse(signature[64]).unwrap(),
+    )
+    .unwrap();
+
+    Keccak256::digest(&public_key.serialize()[1..])[12..]
+        .try_into()
+        .unwrap()
+}
+
+/// Builds the secp256k1 precompile instruction verifying all data packages of the `payload`,
+/// which starts at `payload_offset` of the data of the instruction at `payload_instruction_index`.
+/// The messages are read from that instruction, the signatures are copied as the precompile
+/// expects the recovery id 0 or 1 instead of the 27 or 28 used by RedStone.
+fn secp256k1_instruction(
+    payload: &[u8],
+    payload_offset: usize,
+    payload_instruction_index: u8,
+) -> Instruction {
+    let packages = data_packages(payload);
+    let offset = |position: usize| u16::try_from(position).unwrap().to_le_bytes();
+
+    let mut data = vec![u8::try_from(packages.len()).unwrap()];
+    let mut verified = vec![];
+    let verified_offset = 1 + packages.len() * SECP256K1_SIGNATURE_OFFSETS_BS;
+
+    for (message, signature) in packages {
+        let address_offset = verified_offset + verified.len();
+        let message_bytes = &payload[message.clone()];
+        let mut signature_bytes = payload[signature].to_vec();
+        signature_bytes[64] = signature_bytes[64]
+            .checked_sub(27)
+            .unwrap_or(signature_bytes[64]);
+
+        verified.extend_from_slice(&signer_address(message_bytes, &signature_bytes));
+        verified.extend_from_slice(&signature_bytes);
+
+        data.extend_from_slice(&offset(address_offset + ETH_ADDRESS_BS));
+        data.push(0);
+        data.extend_from_slice(&offset(address_offset));
+        data.push(0);
+        data.extend_from_slice(&offset(payload_offset + message.start));
+        data.extend_from_slice(&offset(message.len()));
+        data.push(payload_instruction_index);
+    }
+    data.append(&mut verified);
+
+    Instruction {
+        program_id: SECP256K1_PROGRAM.parse().unwrap(),
+        accounts: vec![],
+        data,
+    }
 }
 
 fn initialize_svm() -> (LiteSVM, Keypair, Pubkey, DummyKeypair) {
@@ -96,7 +268,7 @@ fn initialize_svm() -> (LiteSVM, Keypair, Pubkey, DummyKeypair) {
 
     let signer = Keypair::new();
     let trusted_keypair = trusted_keypair();
-    let program_pubkey = PROGRAM_ID.parse().unwrap();
+    let program_pubkey = pubkey(redstone_solana_client::ID);
 
     svm.add_program_from_file(program_pubkey, PROGRAM_PATH)
         .unwrap();
@@ -117,14 +289,97 @@ impl Env {
     pub fn new() -> Self {
         let (svm, signer, program_id, trusted_signer) = initialize_svm();
 
-        Self {
+        let mut env = Self {
             svm,
             signer,
             program_id,
             trusted_signer,
+            batch_writes: false,
+            buffered_writes: false,
+            price_history: false,
+            allow_feeds: true,
+            feed_metadata: None,
+            pause_feeds: false,
+            precompile_verification: false,
+            logs: Default::default(),
+            compute_units: Default::default(),
+        };
+        env.initialize_config();
+
+        env
+    }
+
+    /// Makes `process_payload` write all feeds with a single `write_prices`
+    /// instruction instead of one `write_price` per feed.
+    pub fn with_batch_writes(self) -> Self {
+        Self {
+            batch_writes: true,
+            ..self
         }
     }
 
+    /// Makes `process_payload` write all feeds with a single `write_prices_from_buffer`
+    /// instruction, after uploading the payload to a buffer in chunks.
+    pub fn with_buffered_writes(self) -> Self {
+        Self {
+            buffered_writes: true,
+            ..self
+        }
+    }
+
+    /// Makes `process_payload` append the written values to the `PriceHistory` accounts
+    /// of the feeds, initializing them when needed.
+    pub fn with_price_history(self) -> Self {
+        Self {
+            price_history: true,
+            ..self
+        }
+    }
+
+    /// Makes `process_payload` write the feeds without adding them to the allowed feeds first.
+    pub fn with_feeds_not_allowed(self) -> Self {
+        Self {
+            allow_feeds: false,
+            ..self
+        }
+    }
+
+    /// Makes `process_payload` set the `metadata` of the feeds before their first write.
+    pub fn with_feed_metadata(self, metadata: FeedMetadata) -> Self {
+        Self {
+            feed_metadata: Some(metadata),
+            ..self
+        }
+    }
+
+    /// Makes `process_payload` pause the feeds before their first write.
+    pub fn with_feeds_paused(self) -> Self {
+        Self {
+            pause_feeds: true,
+            ..self
+        }
+    }
+
+    /// Makes the write instructions verify the signatures of the payload
+    /// by a secp256k1 precompile instruction preceding them in the transaction.
+    pub fn with_precompile_verification(self) -> Self {
+        Self {
+            precompile_verification: true,
+            ..self
+        }
+    }
+
+    /// Logs of the successful write transactions, shared so they can be inspected
+    /// after the env is moved into a scenario.
+    pub fn logs(&self) -> Rc<RefCell<Vec<String>>> {
+        self.logs.clone()
+    }
+
+    /// Compute units consumed by the successful write transactions, shared like the [`Env::logs`].
+    pub fn compute_units(&self) -> Rc<RefCell<Vec<u64>>> {
+        self.compute_units.clone()
+    }
+
     pub fn set_time(&mut self, timestamp_millis: u64) {
         let mut clock: Clock = self.svm.get_sysvar();
         clock.unix_timestamp = (timestamp_millis / 1_000) as i64;
@@ -132,6 +387,15 @@ impl Env {
         self.svm.set_sysvar(&clock);
     }
 
+    /// Moves the clock to the `slot`, also backwards in time, unlike the validator would.
+    pub fn set_clock(&mut self, slot: u64, timestamp_millis: u64) {
+        let mut clock: Clock = self.svm.get_sysvar();
+        clock.slot = slot;
+        clock.unix_timestamp = (timestamp_millis / 1_000) as i64;
+
+        self.svm.set_sysvar(&clock);
+    }
+
     fn advance_clock(&mut self, by_millis: u64) {
         let mut clock: Clock = self.svm.get_sysvar();
         clock.unix_timestamp += (by_millis / 1_000) as i64;
@@ -139,45 +403,316 @@ impl Env {
         self.svm.set_sysvar(&clock);
     }
 
-    fn feed_address(&self, feed_id: Vec<u8>) -> Pubkey {
-        Pubkey::find_program_address(&[&price_seed(), &feed_seed(feed_id)], &self.program_id).0
+    pub fn feed_address(&self, feed_id: Vec<u8>) -> Pubkey {
+        pubkey(pda::price_address(&to_feed_id(&feed_id)))
+    }
+
+    fn config_address(&self) -> Pubkey {
+        pubkey(pda::config_address())
+    }
+
+    fn actor_signer(&self, actor: Actor) -> Box<dyn Signer + '_> {
+        match actor {
+            Actor::Trusted => Box::new(&self.trusted_signer),
+            Actor::Untrusted => Box::new(&self.signer),
+        }
+    }
+
+    pub fn actor_pubkey(&self, actor: Actor) -> Pubkey {
+        self.actor_signer(actor).pubkey()
+    }
+
+    fn actor_client_pubkey(&self, actor: Actor) -> ClientPubkey {
+        client_pubkey(self.actor_pubkey(actor))
+    }
+
+    pub fn read_config(&self) -> ConfigAccount {
+        let account = self.svm.get_account(&self.config_address()).unwrap();
+
+        accounts::decode_config_account(&account.data).unwrap()
+    }
+
+    /// The `ConfigParams` of the current config, to be modified before [`Env::update_config`].
+    pub fn read_config_params(&self) -> ConfigParams {
+        let config = self.read_config();
+
+        ConfigParams {
+            signer_count_threshold: config.signer_count_threshold,
+            signers: config.signers,
+            trusted_updaters: config.trusted_updaters,
+            max_timestamp_delay_ms: config.max_timestamp_delay_ms,
+            max_timestamp_ahead_ms: config.max_timestamp_ahead_ms,
+            min_interval_between_updates_ms: config.min_interval_between_updates_ms,
+        }
+    }
+
+    fn initialize_config(&mut self) {
+        let instruction = instruction(instructions::initialize_config(client_pubkey(
+            self.signer.pubkey(),
+        )));
+        let tx = self.transaction(instruction, &self.signer);
+
+        self.svm.send_transaction(tx).unwrap();
+    }
+
+    /// Sends the `instruction` built for the pubkey of the `actor`, signed by the `actor`.
+    fn send_actor_instruction(
+        &mut self,
+        instruction: ClientInstruction,
+        actor: Actor,
+    ) -> TransactionMetadata {
+        let tx = {
+            let signer = self.actor_signer(actor);
+
+            self.transaction(self::instruction(instruction), &signer)
+        };
+
+        self.svm.send_transaction(tx).unwrap()
+    }
+
+    pub fn update_config(&mut self, params: ConfigParams, actor: Actor) {
+        let instruction = instructions::update_config(self.actor_client_pubkey(actor), params);
+
+        self.send_actor_instruction(instruction, actor);
+    }
+
+    pub fn set_feed_allowed(&mut self, feed_id: Vec<u8>, allowed: bool, actor: Actor) {
+        let instruction = instructions::set_feed_allowed(
+            self.actor_client_pubkey(actor),
+            to_feed_id(&feed_id),
+            allowed,
+        );
+
+        self.send_actor_instruction(instruction, actor);
+    }
+
+    pub fn set_pauser(&mut self, pauser: Pubkey, actor: Actor) {
+        let instruction =
+            instructions::set_pauser(self.actor_client_pubkey(actor), client_pubkey(pauser));
+
+        self.send_actor_instruction(instruction, actor);
+    }
+
+    pub fn set_updater_allowed(&mut self, updater: Pubkey, allowed: bool, actor: Actor) {
+        let instruction = instructions::set_updater_allowed(
+            self.actor_client_pubkey(actor),
+            client_pubkey(updater),
+            allowed,
+        );
+
+        self.send_actor_instruction(instruction, actor);
+    }
+
+    pub fn set_updater_allow_list_enabled(&mut self, enabled: bool, actor: Actor) {
+        let instruction =
+            instructions::set_updater_allow_list_enabled(self.actor_client_pubkey(actor), enabled);
+
+        self.send_actor_instruction(instruction, actor);
+    }
+
+    pub fn set_paused(&mut self, paused: bool, actor: Actor) {
+        let instruction = instructions::set_paused(self.actor_client_pubkey(actor), paused);
+
+        self.send_actor_instruction(instruction, actor);
+    }
+
+    pub fn set_feed_paused(&mut self, feed_id: Vec<u8>, paused: bool, actor: Actor) {
+        let instruction = instructions::set_feed_paused(
+            self.actor_client_pubkey(actor),
+            to_feed_id(&feed_id),
+            paused,
+        );
+
+        self.send_actor_instruction(instruction, actor);
+    }
+
+    /// Closes also the `PriceHistory` and the `ScaledPriceData` accounts of the feed, if they exist.
+    pub fn close_price_account(&mut self, feed_id: Vec<u8>, receiver: Pubkey, actor: Actor) {
+        let with_history = self
+            .svm
+            .get_account(&self.price_history_address(feed_id.clone()))
+            .is_some();
+        let with_scaled_price = self
+            .svm
+            .get_account(&self.scaled_price_address(feed_id.clone()))
+            .is_some();
+
+        let instruction = instructions::close_price_account(
+            self.actor_client_pubkey(actor),
+            to_feed_id(&feed_id),
+            client_pubkey(receiver),
+            with_history,
+            with_scaled_price,
+        );
+
+        self.send_actor_instruction(instruction, actor);
+    }
+
+    pub fn set_feed_metadata(&mut self, feed_id: Vec<u8>, metadata: FeedMetadata, actor: Actor) {
+        let instruction = instructions::set_feed_metadata(
+            self.actor_client_pubkey(actor),
+            to_feed_id(&feed_id),
+            metadata,
+        );
+
+        self.send_actor_instruction(instruction, actor);
+    }
+
+    pub fn realloc_price_account(&mut self, feed_id: Vec<u8>) {
+        let instruction = instructions::realloc_price_account(
+            self.actor_client_pubkey(Actor::Untrusted),
+            to_feed_id(&feed_id),
+        );
+
+        self.send_actor_instruction(instruction, Actor::Untrusted);
+    }
+
+    /// Shrinks the price account, as if it was created by a former version of the program.
+    pub fn truncate_price_account(&mut self, feed_id: Vec<u8>, len: usize) {
+        let address = self.feed_address(feed_id);
+        let mut account = self.svm.get_account(&address).unwrap();
+        account.data.truncate(len);
+
+        self.svm.set_account(address, account).unwrap();
+    }
+
+    pub fn price_account(&self, feed_id: Vec<u8>) -> Option<Account> {
+        self.svm
+            .get_account(&self.feed_address(feed_id))
+            .filter(|account| account.lamports > 0)
+    }
+
+    pub fn balance(&self, address: &Pubkey) -> u64 {
+        self.svm.get_balance(address).unwrap_or_default()
+    }
+
+    pub fn transfer_admin(&mut self, new_admin: Pubkey, actor: Actor) {
+        let instruction =
+            instructions::transfer_admin(self.actor_client_pubkey(actor), client_pubkey(new_admin));
+
+        self.send_actor_instruction(instruction, actor);
+    }
+
+    pub fn accept_admin(&mut self, actor: Actor) {
+        let instruction = instructions::accept_admin(self.actor_client_pubkey(actor));
+
+        self.send_actor_instruction(instruction, actor);
     }
 
     pub fn read_price_feed(&self, feed_id: Vec<u8>) -> PriceData {
         let account = self.svm.get_account(&self.feed_address(feed_id)).unwrap();
 
-        PriceData::deserialize(&mut &account.data[8..]).unwrap()
+        accounts::decode_price_data(&account.data).unwrap()
     }
 
-    pub fn write_price(&mut self, feed_id: Vec<u8>, payload: Vec<u8>, signer: Actor) {
-        let instruction_data = write_price_instruction_data(payload, feed_id.clone());
+    pub fn scaled_price_address(&self, feed_id: Vec<u8>) -> Pubkey {
+        pubkey(pda::scaled_price_address(&to_feed_id(&feed_id)))
+    }
 
-        let signer: Box<dyn Signer> = match signer {
-            Actor::Trusted => Box::new(&self.trusted_signer),
-            Actor::Untrusted => Box::new(&self.signer),
+    /// Stores the `PriceData` of the feed directly, bypassing the payload verification.
+    pub fn set_price_data(&mut self, feed_id: Vec<u8>, value: [u8; 32], timestamp: u64) {
+        let account = self.price_data_account(feed_id.clone(), value, timestamp);
+
+        self.set_account(self.feed_address(feed_id), account);
+    }
+
+    /// The `PriceData` account as written by the program, to be modified before [`Env::set_account`].
+    pub fn price_data_account(&self, feed_id: Vec<u8>, value: [u8; 32], timestamp: u64) -> Account {
+        self.price_data_account_written_at(feed_id, value, timestamp, timestamp)
+    }
+
+    pub fn price_data_account_written_at(
+        &self,
+        feed_id: Vec<u8>,
+        value: [u8; 32],
+        timestamp: u64,
+        write_timestamp: u64,
+    ) -> Account {
+        let price_data = PriceData {
+            feed_id: to_feed_id(&feed_id),
+            value,
+            timestamp,
+            write_timestamp: Some(write_timestamp),
+            write_slot_number: self.svm.get_sysvar::<Clock>().slot,
+            decimals: 8,
+            metadata: Default::default(),
+            paused: false,
+            ..Default::default()
         };
 
-        let instruction = Instruction {
-            program_id: self.program_id,
-            accounts: vec![
-                AccountMeta::new(signer.pubkey(), true),
-                AccountMeta::new(self.feed_address(feed_id), false),
-                AccountMeta::new_readonly(SYSTEM_PROGRAM.parse().unwrap(), false),
-            ],
-            data: instruction_data,
+        let mut data = vec![];
+        price_data.try_serialize(&mut data).unwrap();
+        data.resize(PRICE_DATA_SPACE, 0);
+
+        Account {
+            lamports: LAMPORTS_PER_SOL,
+            data,
+            owner: self.program_id,
+            executable: false,
+            rent_epoch: 0,
+        }
+    }
+
+    pub fn set_account(&mut self, address: Pubkey, account: Account) {
+        self.svm.set_account(address, account).unwrap();
+    }
+
+    /// Builds the metas of any accounts, including the ones the instruction doesn't expect.
+    pub fn account_metas(&self, accounts: &[(TestAccount, bool)]) -> Vec<AccountMeta> {
+        accounts
+            .iter()
+            .map(|(account, writable)| {
+                let (pubkey, is_signer) = match account {
+                    TestAccount::Actor(actor) => (self.actor_pubkey(*actor), true),
+                    TestAccount::Feed(feed_id) => (self.feed_address(feed_id.clone()), false),
+                    TestAccount::PriceHistory(feed_id) => {
+                        (self.price_history_address(feed_id.clone()), false)
+                    }
+                    TestAccount::ScaledPrice(feed_id) => {
+                        (self.scaled_price_address(feed_id.clone()), false)
+                    }
+                    TestAccount::Config => (self.config_address(), false),
+                    TestAccount::SystemProgram => (SYSTEM_PROGRAM.parse().unwrap(), false),
+                    TestAccount::Address(pubkey) => (*pubkey, false),
+                };
+
+                AccountMeta {
+                    pubkey,
+                    is_signer,
+                    is_writable: *writable,
+                }
+            })
+            .collect()
+    }
+
+    pub fn send_instruction(&mut self, data: Vec<u8>, accounts: Vec<AccountMeta>, actor: Actor) {
+        let tx = {
+            let signer = self.actor_signer(actor);
+            let instruction = Instruction {
+                program_id: self.program_id,
+                accounts,
+                data,
+            };
+
+            self.transaction(instruction, &signer)
         };
-        let tx = self.transaction(instruction, &signer);
 
         self.svm.send_transaction(tx).unwrap();
     }
 
-    pub fn signer_count(&self) -> u8 {
+    /// Returns the return data of the instruction.
+    pub fn simulate_instruction(&self, data: Vec<u8>, accounts: Vec<AccountMeta>) -> Vec<u8> {
         let instruction = Instruction {
             program_id: self.program_id,
-            accounts: vec![],
-            data: UNIQUE_SIGNER_THRESHOLD_DISCRIMINATOR.to_vec(),
+            accounts,
+            data,
         };
 
+        self.simulate(instruction)
+    }
+
+    /// Returns the return data of the read `instruction`.
+    fn simulate(&self, instruction: Instruction) -> Vec<u8> {
         let tx = self.transaction(instruction, &self.signer);
 
         self.svm
@@ -185,7 +720,280 @@ impl Env {
             .unwrap()
             .meta
             .return_data
-            .data[0]
+            .data
+    }
+
+    pub fn price_checked(&self, feed_id: Vec<u8>, max_age_ms: u64) -> [u8; 32] {
+        let return_data = self.simulate(instruction(instructions::price_checked(
+            to_feed_id(&feed_id),
+            max_age_ms,
+        )));
+
+        return_data.try_into().unwrap()
+    }
+
+    pub fn read_price_scaled(&self, feed_id: Vec<u8>) -> ScaledPrice {
+        let return_data = self.simulate(instruction(instructions::read_price_scaled(to_feed_id(
+            &feed_id,
+        ))));
+
+        ScaledPrice::deserialize(&mut &return_data[..]).unwrap()
+    }
+
+    pub fn read_derived_price(
+        &self,
+        components: &[(Vec<u8>, DerivedPriceOperation)],
+        decimals: u8,
+    ) -> DerivedPrice {
+        let formula = DerivedPriceFormula {
+            components: components
+                .iter()
+                .map(|(feed_id, operation)| DerivedPriceComponent {
+                    feed_id: to_feed_id(feed_id),
+                    operation: *operation,
+                })
+                .collect(),
+            decimals,
+        };
+        let return_data = self.simulate(instruction(instructions::read_derived_price(formula)));
+
+        DerivedPrice::deserialize(&mut &return_data[..]).unwrap()
+    }
+
+    pub fn update_scaled_price(&mut self, feed_id: Vec<u8>) {
+        let instruction = instructions::update_scaled_price(
+            self.actor_client_pubkey(Actor::Untrusted),
+            to_feed_id(&feed_id),
+        );
+        // the same update can be sent repeatedly
+        self.svm.expire_blockhash();
+
+        self.send_actor_instruction(instruction, Actor::Untrusted);
+    }
+
+    pub fn read_scaled_price_feed(&self, feed_id: Vec<u8>) -> ScaledPriceData {
+        let account = self
+            .svm
+            .get_account(&self.scaled_price_address(feed_id))
+            .unwrap();
+
+        accounts::decode_scaled_price_data(&account.data).unwrap()
+    }
+
+    pub fn price_history_address(&self, feed_id: Vec<u8>) -> Pubkey {
+        pubkey(pda::price_history_address(&to_feed_id(&feed_id)))
+    }
+
+    pub fn init_price_history(&mut self, feed_id: Vec<u8>) {
+        let instruction = instructions::init_price_history(
+            self.actor_client_pubkey(Actor::Untrusted),
+            to_feed_id(&feed_id),
+        );
+
+        self.send_actor_instruction(instruction, Actor::Untrusted);
+    }
+
+    /// Stores the `PriceHistory` of the feed directly, the `entries` given from the oldest one.
+    pub fn set_price_history(&mut self, feed_id: Vec<u8>, entries: &[HistoricalPrice]) {
+        let mut data = PriceHistory::DISCRIMINATOR.to_vec();
+        data.extend_from_slice(&to_feed_id(&feed_id));
+        data.extend_from_slice(&((entries.len() % PRICE_HISTORY_CAPACITY) as u64).to_le_bytes());
+        data.extend_from_slice(&(entries.len() as u64).to_le_bytes());
+        for entry in entries {
+            data.extend_from_slice(&entry.value);
+            data.extend_from_slice(&entry.timestamp.to_le_bytes());
+            data.extend_from_slice(&entry.write_slot.to_le_bytes());
+        }
+        data.resize(
+            data.len()
+                + (PRICE_HISTORY_CAPACITY - entries.len())
+                    * std::mem::size_of::<PriceHistoryEntry>(),
+            0,
+        );
+
+        let account = Account {
+            lamports: LAMPORTS_PER_SOL,
+            data,
+            owner: self.program_id,
+            executable: false,
+            rent_epoch: 0,
+        };
+
+        self.svm
+            .set_account(self.price_history_address(feed_id), account)
+            .unwrap();
+    }
+
+    pub fn latest_price_history(&self, feed_id: Vec<u8>, count: u8) -> Vec<HistoricalPrice> {
+        let return_data = self.simulate(instruction(instructions::latest_price_history(
+            to_feed_id(&feed_id),
+            count,
+        )));
+
+        Vec::<HistoricalPrice>::deserialize(&mut &return_data[..]).unwrap()
+    }
+
+    pub fn price_twap(&self, feed_id: Vec<u8>, window_ms: u64) -> [u8; 32] {
+        let return_data = self.simulate(instruction(instructions::price_twap(
+            to_feed_id(&feed_id),
+            window_ms,
+        )));
+
+        return_data.try_into().unwrap()
+    }
+
+    fn write_options(&self) -> WriteOptions {
+        WriteOptions {
+            with_history: self.price_history,
+            precompile_verification: self.precompile_verification,
+            ..Default::default()
+        }
+    }
+
+    pub fn write_price(&mut self, feed_id: Vec<u8>, payload: Vec<u8>, signer: Actor) {
+        let instruction = instructions::write_price(
+            self.actor_client_pubkey(signer),
+            to_feed_id(&feed_id),
+            payload.clone(),
+            self.write_options(),
+        );
+
+        self.send_write_instruction(instruction, &payload, signer);
+    }
+
+    pub fn write_prices(&mut self, feed_ids: Vec<Vec<u8>>, payload: Vec<u8>, signer: Actor) {
+        let instruction = instructions::write_prices(
+            self.actor_client_pubkey(signer),
+            feed_ids.iter().map(|feed_id| to_feed_id(feed_id)).collect(),
+            payload.clone(),
+            self.write_options(),
+        );
+
+        self.send_write_instruction(instruction, &payload, signer);
+    }
+
+    pub fn payload_buffer_address(&self, buffer_id: u64, actor: Actor) -> Pubkey {
+        pubkey(pda::payload_buffer_address(
+            &self.actor_client_pubkey(actor),
+            buffer_id,
+        ))
+    }
+
+    pub fn payload_buffer(&self, buffer_id: u64, actor: Actor) -> Option<Account> {
+        self.svm
+            .get_account(&self.payload_buffer_address(buffer_id, actor))
+            .filter(|account| account.lamports > 0)
+    }
+
+    pub fn init_payload_buffer(&mut self, buffer_id: u64, capacity: u32, actor: Actor) {
+        let instruction =
+            instructions::init_payload_buffer(self.actor_client_pubkey(actor), buffer_id, capacity);
+
+        self.send_actor_instruction(instruction, actor);
+    }
+
+    pub fn append_payload_chunk(
+        &mut self,
+        buffer_id: u64,
+        offset: u32,
+        chunk: &[u8],
+        actor: Actor,
+    ) {
+        let instruction = instructions::append_payload_chunk(
+            self.actor_client_pubkey(actor),
+            buffer_id,
+            offset,
+            chunk.to_vec(),
+        );
+
+        self.send_actor_instruction(instruction, actor);
+    }
+
+    pub fn close_payload_buffer(&mut self, buffer_id: u64, actor: Actor) {
+        let instruction =
+            instructions::close_payload_buffer(self.actor_client_pubkey(actor), buffer_id);
+
+        self.send_actor_instruction(instruction, actor);
+    }
+
+    /// Uploads the `payload` to the buffer in chunks of `PAYLOAD_CHUNK_SIZE` bytes.
+    pub fn upload_payload(&mut self, buffer_id: u64, payload: &[u8], actor: Actor) {
+        // the buffer can be created again with the same id after being consumed
+        self.svm.expire_blockhash();
+        self.init_payload_buffer(buffer_id, payload.len() as u32, actor);
+
+        let append_instructions = instructions::append_payload_chunks(
+            self.actor_client_pubkey(actor),
+            buffer_id,
+            payload,
+            PAYLOAD_CHUNK_SIZE,
+        );
+        for instruction in append_instructions {
+            self.send_actor_instruction(instruction, actor);
+        }
+    }
+
+    pub fn write_prices_from_buffer(
+        &mut self,
+        buffer_id: u64,
+        feed_ids: Vec<Vec<u8>>,
+        payload_hash: [u8; 32],
+        actor: Actor,
+    ) {
+        let instruction = instructions::write_prices_from_buffer(
+            self.actor_client_pubkey(actor),
+            buffer_id,
+            feed_ids.iter().map(|feed_id| to_feed_id(feed_id)).collect(),
+            payload_hash,
+            WriteOptions {
+                precompile_verification: false,
+                ..self.write_options()
+            },
+        );
+
+        let meta = self.send_actor_instruction(instruction, actor);
+        self.record(meta);
+    }
+
+    pub fn signer_count(&self) -> u8 {
+        self.simulate(instruction(instructions::unique_signers_count()))[0]
+    }
+
+    /// Keeps the logs and the compute units of the successful write transaction.
+    fn record(&self, meta: TransactionMetadata) {
+        self.logs.borrow_mut().extend(meta.logs);
+        self.compute_units
+            .borrow_mut()
+            .push(meta.compute_units_consumed);
+    }
+
+    /// Precedes the write `instruction` with the secp256k1 precompile instruction
+    /// when the precompile verification is enabled.
+    fn send_write_instruction(
+        &mut self,
+        instruction: ClientInstruction,
+        payload: &[u8],
+        actor: Actor,
+    ) {
+        let instruction = self::instruction(instruction);
+        let tx = {
+            let signer = self.actor_signer(actor);
+            let mut instructions = vec![];
+            if self.precompile_verification {
+                let payload_offset = instruction.data.len() - payload.len();
+                instructions.push(secp256k1_instruction(payload, payload_offset, 1));
+            }
+            instructions.push(instruction);
+
+            Transaction::new(
+                &[&signer],
+                Message::new(&instructions, Some(&signer.pubkey())),
+                self.svm.latest_blockhash(),
+            )
+        };
+
+        let meta = self.svm.send_transaction(tx).unwrap();
+        self.record(meta);
     }
 
     fn transaction(&self, instruction: Instruction, signer: &impl Signer) -> Transaction {
@@ -200,8 +1008,7 @@ impl Env {
 impl PriceAdapterRunEnv for Env {
     fn read_timestamp(&mut self, feed_id: Option<&str>) -> u64 {
         if let Some(feed_id) = feed_id {
-            self.read_price_feed(feed_seed(feed_id.as_bytes().to_vec()))
-                .timestamp
+            self.read_price_feed(feed_id.as_bytes().to_vec()).timestamp
         } else {
             0
         }
@@ -218,6 +1025,52 @@ impl PriceAdapterRunEnv for Env {
     }
 
     fn process_payload(&mut self, payload: Vec<u8>, feed_ids: Vec<Vec<u8>>, signer: Actor) {
+        if self.allow_feeds {
+            let allowed_feed_ids = self.read_config().allowed_feed_ids;
+            for feed in &feed_ids {
+                if !allowed_feed_ids.contains(&to_feed_id(feed)) {
+                    self.set_feed_allowed(feed.clone(), true, Actor::Trusted);
+                }
+            }
+        }
+
+        if let Some(metadata) = self.feed_metadata {
+            for feed in &feed_ids {
+                if self.price_account(feed.clone()).is_none() {
+                    self.set_feed_metadata(feed.clone(), metadata, Actor::Trusted);
+                }
+            }
+        }
+
+        if self.pause_feeds {
+            for feed in &feed_ids {
+                if self.price_account(feed.clone()).is_none() {
+                    self.set_feed_metadata(feed.clone(), Default::default(), Actor::Trusted);
+                    self.set_feed_paused(feed.clone(), true, Actor::Trusted);
+                }
+            }
+        }
+
+        if self.price_history {
+            for feed in &feed_ids {
+                let address = self.price_history_address(feed.clone());
+                if self.svm.get_account(&address).is_none() {
+                    self.init_price_history(feed.clone());
+                }
+            }
+        }
+
+        if self.buffered_writes {
+            self.upload_payload(0, &payload, signer);
+            let payload_hash = Sha256::digest(&payload).into();
+
+            return self.write_prices_from_buffer(0, feed_ids, payload_hash, signer);
+        }
+
+        if self.batch_writes {
+            return self.write_prices(feed_ids, payload, signer);
+        }
+
         for feed in feed_ids {
             self.write_price(feed.clone(), payload.clone(), signer);
         }
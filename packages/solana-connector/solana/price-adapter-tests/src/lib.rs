@@ -1,22 +1,33 @@
 #[cfg(test)]
 use {
     core::time::Duration,
-    environment::Env,
-    redstone_testing::primary_signers_scenarios::{
-        scenario_adapter_update_with_almost_future_timestamp,
-        scenario_adapter_update_with_almost_old_timestamp,
-        scenario_adapter_update_with_future_timestamp, scenario_adapter_update_with_old_timestamp,
-        scenario_check_initalization, scenario_missing_feed_in_payload,
-        scenario_trusted_updates_twice_without_waiting_for_threshold,
-        scenario_untrusted_updates_twice_waiting_for_threshold,
-        scenario_untrusted_updates_twice_without_waiting_for_threshold,
-        scenario_updating_twice_with_the_same_timestamp, scenario_updating_with_only_2_signers,
-        scenario_with_5_signers,
+    environment::{scaled_price, Env, PAYLOAD_CHUNK_SIZE},
+    redstone_solana_client::state::{
+        DerivedPrice, DerivedPriceOperation, FeedMetadata, HistoricalPrice, ScaledPrice,
+        PRICE_DATA_SPACE,
+    },
+    redstone_testing::{
+        primary_signers_scenarios::{
+            scenario_adapter_update_with_almost_future_timestamp,
+            scenario_adapter_update_with_almost_old_timestamp,
+            scenario_adapter_update_with_future_timestamp,
+            scenario_adapter_update_with_old_timestamp, scenario_check_initalization,
+            scenario_missing_feed_in_payload,
+            scenario_trusted_updates_twice_without_waiting_for_threshold,
+            scenario_untrusted_updates_twice_waiting_for_threshold,
+            scenario_untrusted_updates_twice_without_waiting_for_threshold,
+            scenario_updating_twice_with_the_same_timestamp, scenario_updating_with_only_2_signers,
+            scenario_with_5_signers,
+        },
+        signer::ContractUpdateSigner as Actor,
     },
 };
 
 pub mod environment;
 
+#[cfg(test)]
+mod adversarial;
+
 #[test]
 fn check_initalization() {
     let env = Env::new();
@@ -125,3 +136,922 @@ fn adapter_update_with_almost_future_timestamp() {
 
     scenario.run(env);
 }
+
+#[test]
+fn batch_check_initalization() {
+    let env = Env::new().with_batch_writes();
+    let scenario = scenario_check_initalization();
+
+    scenario.run(env);
+}
+
+#[test]
+#[should_panic(expected = "Error Code: rust-sdk. Error Number: 1102")]
+fn batch_untrusted_updates_twice_without_waiting_for_threshold() {
+    let env = Env::new().with_batch_writes();
+    let scenario =
+        scenario_untrusted_updates_twice_without_waiting_for_threshold(Duration::from_secs(40));
+
+    scenario.run(env);
+}
+
+#[test]
+fn batch_trusted_updates_twice_without_waiting_for_threshold() {
+    let env = Env::new().with_batch_writes();
+    let scenario =
+        scenario_trusted_updates_twice_without_waiting_for_threshold(Duration::from_secs(40));
+
+    scenario.run(env);
+}
+
+#[test]
+#[should_panic(
+    expected = "Error Code: rust-sdk. Error Number: 510. Error Message: Array is empty."
+)]
+fn batch_missing_feed_in_payload() {
+    let env = Env::new().with_batch_writes();
+    let scenario = scenario_missing_feed_in_payload();
+
+    scenario.run(env);
+}
+
+#[test]
+fn batch_with_5_signers() {
+    let env = Env::new().with_batch_writes();
+    let scenario = scenario_with_5_signers(Duration::from_secs(40));
+
+    scenario.run(env);
+}
+
+#[test]
+fn admin_transfer_is_two_step() {
+    let mut env = Env::new();
+    let trusted = env.actor_pubkey(Actor::Trusted);
+    let untrusted = env.actor_pubkey(Actor::Untrusted);
+
+    env.transfer_admin(untrusted, Actor::Trusted);
+
+    let config = env.read_config();
+    assert_eq!(config.admin.to_bytes(), trusted.to_bytes());
+    assert_eq!(
+        config.pending_admin.map(|admin| admin.to_bytes()),
+        Some(untrusted.to_bytes())
+    );
+
+    env.accept_admin(Actor::Untrusted);
+
+    let config = env.read_config();
+    assert_eq!(config.admin.to_bytes(), untrusted.to_bytes());
+    assert_eq!(config.pending_admin, None);
+}
+
+#[test]
+#[should_panic(expected = "Error Code: NotPendingAdmin")]
+fn accept_admin_without_transfer() {
+    let mut env = Env::new();
+
+    env.accept_admin(Actor::Untrusted);
+}
+
+#[test]
+#[should_panic(expected = "Error Code: NotAdmin")]
+fn update_config_by_not_admin() {
+    let mut env = Env::new();
+    let params = env.read_config_params();
+
+    env.update_config(params, Actor::Untrusted);
+}
+
+#[test]
+fn update_config_changes_signer_count_threshold() {
+    let mut env = Env::new();
+    let mut params = env.read_config_params();
+    assert_eq!(env.signer_count(), params.signer_count_threshold);
+
+    params.signer_count_threshold = 5;
+    env.update_config(params, Actor::Trusted);
+
+    assert_eq!(env.signer_count(), 5);
+}
+
+#[test]
+#[should_panic(expected = "Error Code: rust-sdk")]
+fn update_config_with_duplicated_signers() {
+    let mut env = Env::new();
+    let mut params = env.read_config_params();
+
+    params.signers.push(params.signers[0]);
+    env.update_config(params, Actor::Trusted);
+}
+
+#[test]
+#[should_panic(expected = "rust-sdk. Error Number: 1102")]
+fn trusted_updater_removed_from_config() {
+    let mut env = Env::new();
+    let mut params = env.read_config_params();
+
+    params.trusted_updaters.clear();
+    env.update_config(params, Actor::Trusted);
+
+    let scenario =
+        scenario_trusted_updates_twice_without_waiting_for_threshold(Duration::from_secs(40));
+
+    scenario.run(env);
+}
+
+#[cfg(test)]
+fn u256_value(value: u128) -> [u8; 32] {
+    let mut bytes = [0u8; 32];
+    bytes[16..].copy_from_slice(&value.to_be_bytes());
+
+    bytes
+}
+
+#[test]
+fn read_price_scaled() {
+    let mut env = Env::new();
+    env.set_price_data(
+        b"ETH".to_vec(),
+        u256_value(234_567_000_000),
+        1_744_000_123_456,
+    );
+
+    let scaled_price = env.read_price_scaled(b"ETH".to_vec());
+
+    assert_eq!(
+        scaled_price,
+        ScaledPrice {
+            price: 234_567_000_000,
+            conf: 0,
+            expo: -8,
+            publish_time: 1_744_000_123,
+        }
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error Code: PriceOverflow")]
+fn read_price_scaled_overflow() {
+    let mut env = Env::new();
+    env.set_price_data(
+        b"ETH".to_vec(),
+        u256_value(i64::MAX as u128 + 1),
+        1_744_000_123_456,
+    );
+
+    env.read_price_scaled(b"ETH".to_vec());
+}
+
+#[test]
+fn update_scaled_price_mirrors_price_data() {
+    let mut env = Env::new();
+    env.set_price_data(
+        b"BTC".to_vec(),
+        u256_value(9_876_543_210_000),
+        1_744_000_000_000,
+    );
+    env.update_scaled_price(b"BTC".to_vec());
+
+    env.set_price_data(
+        b"BTC".to_vec(),
+        u256_value(9_800_000_000_000),
+        1_744_000_060_000,
+    );
+    env.update_scaled_price(b"BTC".to_vec());
+
+    let scaled_price_data = env.read_scaled_price_feed(b"BTC".to_vec());
+
+    assert_eq!(&scaled_price_data.feed_id[..3], b"BTC");
+    assert_eq!(
+        scaled_price(&scaled_price_data),
+        env.read_price_scaled(b"BTC".to_vec())
+    );
+    assert_eq!(scaled_price_data.price, 9_800_000_000_000);
+    assert_eq!(scaled_price_data.publish_time, 1_744_000_060);
+}
+
+#[test]
+fn price_checked_within_max_age() {
+    let mut env = Env::new();
+    env.set_price_data(
+        b"ETH".to_vec(),
+        u256_value(234_567_000_000),
+        1_744_000_000_000,
+    );
+    env.set_time(1_744_000_060_000);
+
+    assert_eq!(
+        env.price_checked(b"ETH".to_vec(), 60_000),
+        u256_value(234_567_000_000)
+    );
+}
+
+#[test]
+fn price_checked_with_package_ahead_of_clock() {
+    let mut env = Env::new();
+    env.set_price_data(
+        b"ETH".to_vec(),
+        u256_value(234_567_000_000),
+        1_744_000_030_000,
+    );
+    env.set_time(1_744_000_000_000);
+
+    assert_eq!(
+        env.price_checked(b"ETH".to_vec(), 0),
+        u256_value(234_567_000_000)
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error Code: StalePrice")]
+fn price_checked_stale() {
+    let mut env = Env::new();
+    env.set_price_data(
+        b"ETH".to_vec(),
+        u256_value(234_567_000_000),
+        1_744_000_000_000,
+    );
+    env.set_time(1_744_000_061_000);
+
+    env.price_checked(b"ETH".to_vec(), 60_000);
+}
+
+#[test]
+#[should_panic(expected = "Error Code: StalePriceWrite")]
+fn price_checked_stale_write() {
+    let mut env = Env::new();
+    let account = env.price_data_account_written_at(
+        b"ETH".to_vec(),
+        u256_value(234_567_000_000),
+        1_744_000_030_000,
+        1_743_999_940_000,
+    );
+    env.set_account(env.feed_address(b"ETH".to_vec()), account);
+    env.set_time(1_744_000_060_000);
+
+    env.price_checked(b"ETH".to_vec(), 60_000);
+}
+
+#[test]
+#[should_panic(expected = "Error Code: PriceNotInitialized")]
+fn price_checked_not_initialized() {
+    let mut env = Env::new();
+    env.set_price_data(b"ETH".to_vec(), [0; 32], 0);
+    env.set_time(1_744_000_000_000);
+
+    env.price_checked(b"ETH".to_vec(), u64::MAX);
+}
+
+#[test]
+fn read_derived_price() {
+    let mut env = Env::new();
+    env.set_price_data(
+        b"ETH".to_vec(),
+        u256_value(234_567_000_000),
+        1_744_000_010_000,
+    );
+    env.set_price_data(
+        b"BTC".to_vec(),
+        u256_value(8_345_678_000_000),
+        1_744_000_000_000,
+    );
+
+    let derived = env.read_derived_price(
+        &[
+            (b"ETH".to_vec(), DerivedPriceOperation::Multiply),
+            (b"BTC".to_vec(), DerivedPriceOperation::Divide),
+        ],
+        18,
+    );
+
+    // 2345.67 / 83456.78
+    assert_eq!(
+        derived,
+        DerivedPrice {
+            value: u256_value(28_106_404_296_930_698),
+            decimals: 18,
+            timestamp: 1_744_000_000_000,
+        }
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error Code: PriceNotInitialized")]
+fn read_derived_price_not_initialized() {
+    let mut env = Env::new();
+    env.set_price_data(
+        b"ETH".to_vec(),
+        u256_value(234_567_000_000),
+        1_744_000_000_000,
+    );
+    env.set_price_data(b"BTC".to_vec(), [0; 32], 0);
+
+    env.read_derived_price(
+        &[
+            (b"ETH".to_vec(), DerivedPriceOperation::Multiply),
+            (b"BTC".to_vec(), DerivedPriceOperation::Divide),
+        ],
+        8,
+    );
+}
+
+#[test]
+fn price_history_with_5_signers() {
+    let env = Env::new().with_price_history();
+    let scenario = scenario_with_5_signers(Duration::from_secs(40));
+
+    scenario.run(env);
+}
+
+#[test]
+fn batch_price_history_trusted_updates_twice_without_waiting_for_threshold() {
+    let env = Env::new().with_batch_writes().with_price_history();
+    let scenario =
+        scenario_trusted_updates_twice_without_waiting_for_threshold(Duration::from_secs(40));
+
+    scenario.run(env);
+}
+
+#[cfg(test)]
+fn historical_price(value: u128, timestamp: u64) -> HistoricalPrice {
+    HistoricalPrice {
+        value: u256_value(value),
+        timestamp,
+        write_slot: timestamp / 400,
+    }
+}
+
+#[test]
+fn latest_price_history() {
+    let mut env = Env::new();
+    let entries: Vec<_> = (1..=25)
+        .map(|i| historical_price(i * 100, 1_744_000_000_000 + i as u64 * 1_000))
+        .collect();
+    env.set_price_history(b"ETH".to_vec(), &entries);
+
+    let latest = env.latest_price_history(b"ETH".to_vec(), 3);
+
+    assert_eq!(
+        latest,
+        entries.iter().rev().take(3).cloned().collect::<Vec<_>>()
+    );
+    assert_eq!(env.latest_price_history(b"ETH".to_vec(), 20).len(), 20);
+}
+
+#[test]
+#[should_panic(expected = "Error Code: TooManyHistoryEntries")]
+fn latest_price_history_too_many_entries() {
+    let mut env = Env::new();
+    env.init_price_history(b"ETH".to_vec());
+
+    env.latest_price_history(b"ETH".to_vec(), 21);
+}
+
+#[test]
+fn price_twap() {
+    let mut env = Env::new();
+    env.set_price_history(
+        b"ETH".to_vec(),
+        &[
+            historical_price(100, 1_744_000_000_000),
+            historical_price(200, 1_744_000_010_000),
+            historical_price(400, 1_744_000_020_000),
+        ],
+    );
+    env.set_time(1_744_000_025_000);
+
+    assert_eq!(
+        env.price_twap(b"ETH".to_vec(), 20_000),
+        u256_value((100 * 5 + 200 * 10 + 400 * 5) / 20)
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error Code: PriceNotInitialized")]
+fn price_twap_of_empty_history() {
+    let mut env = Env::new();
+    env.init_price_history(b"ETH".to_vec());
+    env.set_time(1_744_000_025_000);
+
+    env.price_twap(b"ETH".to_vec(), 20_000);
+}
+
+#[cfg(test)]
+const PRICE_UPDATED_EVENT_LOG: &str = "Program data: mkhXlvbm";
+
+#[test]
+fn price_updated_event() {
+    let env = Env::new();
+    let logs = env.logs();
+    let scenario = scenario_with_5_signers(Duration::from_secs(40));
+
+    scenario.run(env);
+
+    assert!(logs
+        .borrow()
+        .iter()
+        .any(|log| log.starts_with(PRICE_UPDATED_EVENT_LOG)));
+}
+
+#[test]
+fn batch_price_updated_event() {
+    let env = Env::new().with_batch_writes();
+    let logs = env.logs();
+    let scenario = scenario_with_5_signers(Duration::from_secs(40));
+
+    scenario.run(env);
+
+    assert!(logs
+        .borrow()
+        .iter()
+        .any(|log| log.starts_with(PRICE_UPDATED_EVENT_LOG)));
+}
+
+#[test]
+#[should_panic(expected = "Error Code: FeedNotAllowed")]
+fn write_not_allowed_feed() {
+    let env = Env::new().with_feeds_not_allowed();
+    let scenario = scenario_with_5_signers(Duration::from_secs(40));
+
+    scenario.run(env);
+}
+
+#[test]
+#[should_panic(expected = "Error Code: FeedNotAllowed")]
+fn batch_write_not_allowed_feed() {
+    let env = Env::new().with_batch_writes().with_feeds_not_allowed();
+    let scenario = scenario_with_5_signers(Duration::from_secs(40));
+
+    scenario.run(env);
+}
+
+#[test]
+fn set_feed_allowed() {
+    let mut env = Env::new();
+
+    env.set_feed_allowed(b"ETH".to_vec(), true, Actor::Trusted);
+    env.set_feed_allowed(b"BTC".to_vec(), true, Actor::Trusted);
+    env.set_feed_allowed(b"ETH".to_vec(), true, Actor::Trusted);
+    assert_eq!(env.read_config().allowed_feed_ids.len(), 2);
+
+    env.set_feed_allowed(b"ETH".to_vec(), false, Actor::Trusted);
+    let allowed_feed_ids = env.read_config().allowed_feed_ids;
+    assert_eq!(allowed_feed_ids.len(), 1);
+    assert_eq!(&allowed_feed_ids[0][..3], b"BTC");
+}
+
+#[test]
+#[should_panic(expected = "Error Code: NotAdmin")]
+fn set_feed_allowed_by_not_admin() {
+    let mut env = Env::new();
+
+    env.set_feed_allowed(b"ETH".to_vec(), true, Actor::Untrusted);
+}
+
+#[test]
+fn close_price_account() {
+    let mut env = Env::new();
+    let receiver = env.actor_pubkey(Actor::Untrusted);
+    env.set_price_data(
+        b"ETH".to_vec(),
+        u256_value(234_567_000_000),
+        1_744_000_000_000,
+    );
+    let lamports = env.price_account(b"ETH".to_vec()).unwrap().lamports;
+    let balance = env.balance(&receiver);
+
+    env.close_price_account(b"ETH".to_vec(), receiver, Actor::Trusted);
+
+    assert!(env.price_account(b"ETH".to_vec()).is_none());
+    assert_eq!(env.balance(&receiver), balance + lamports);
+}
+
+#[test]
+fn close_price_account_with_history_and_scaled_price() {
+    let mut env = Env::new();
+    let receiver = env.actor_pubkey(Actor::Untrusted);
+    env.set_price_data(
+        b"ETH".to_vec(),
+        u256_value(234_567_000_000),
+        1_744_000_000_000,
+    );
+    env.init_price_history(b"ETH".to_vec());
+    env.update_scaled_price(b"ETH".to_vec());
+    let history_address = env.price_history_address(b"ETH".to_vec());
+    let scaled_price_address = env.scaled_price_address(b"ETH".to_vec());
+    let lamports = env.price_account(b"ETH".to_vec()).unwrap().lamports
+        + env.balance(&history_address)
+        + env.balance(&scaled_price_address);
+    let balance = env.balance(&receiver);
+
+    env.close_price_account(b"ETH".to_vec(), receiver, Actor::Trusted);
+
+    assert!(env.price_account(b"ETH".to_vec()).is_none());
+    assert_eq!(env.balance(&history_address), 0);
+    assert_eq!(env.balance(&scaled_price_address), 0);
+    assert_eq!(env.balance(&receiver), balance + lamports);
+}
+
+#[test]
+#[should_panic(expected = "Error Code: NotAdmin")]
+fn close_price_account_by_not_admin() {
+    let mut env = Env::new();
+    let receiver = env.actor_pubkey(Actor::Untrusted);
+    env.set_price_data(
+        b"ETH".to_vec(),
+        u256_value(234_567_000_000),
+        1_744_000_000_000,
+    );
+
+    env.close_price_account(b"ETH".to_vec(), receiver, Actor::Untrusted);
+}
+
+#[test]
+fn realloc_price_account() {
+    let mut env = Env::new();
+    env.set_price_data(
+        b"ETH".to_vec(),
+        u256_value(234_567_000_000),
+        1_744_000_000_000,
+    );
+    env.truncate_price_account(b"ETH".to_vec(), PRICE_DATA_SPACE - 64);
+
+    env.realloc_price_account(b"ETH".to_vec());
+
+    assert_eq!(
+        env.price_account(b"ETH".to_vec()).unwrap().data.len(),
+        PRICE_DATA_SPACE
+    );
+    assert_eq!(
+        env.read_price_feed(b"ETH".to_vec()).value,
+        u256_value(234_567_000_000)
+    );
+}
+
+#[cfg(test)]
+fn feed_metadata(heartbeat_ms: u64, deviation_threshold_bps: u16) -> FeedMetadata {
+    FeedMetadata {
+        heartbeat_ms,
+        deviation_threshold_bps,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn set_feed_metadata() {
+    let mut env = Env::new();
+    env.set_price_data(
+        b"ETH".to_vec(),
+        u256_value(234_567_000_000),
+        1_744_000_000_000,
+    );
+    let metadata = FeedMetadata {
+        decimals: 18,
+        ..feed_metadata(3_600_000, 50)
+    };
+
+    env.set_feed_metadata(b"ETH".to_vec(), metadata, Actor::Trusted);
+
+    let price_data = env.read_price_feed(b"ETH".to_vec());
+    assert_eq!(price_data.metadata, metadata);
+    assert_eq!(price_data.value, u256_value(234_567_000_000));
+}
+
+#[test]
+fn set_feed_metadata_before_first_write() {
+    let mut env = Env::new();
+    let metadata = feed_metadata(3_600_000, 50);
+
+    env.set_feed_metadata(b"ETH".to_vec(), metadata, Actor::Trusted);
+
+    let price_data = env.read_price_feed(b"ETH".to_vec());
+    assert_eq!(price_data.metadata, metadata);
+    assert_eq!(price_data.write_timestamp, None);
+}
+
+#[test]
+#[should_panic(expected = "Error Code: NotAdmin")]
+fn set_feed_metadata_by_not_admin() {
+    let mut env = Env::new();
+
+    env.set_feed_metadata(
+        b"ETH".to_vec(),
+        feed_metadata(3_600_000, 50),
+        Actor::Untrusted,
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error Code: UpdateNotRequired")]
+fn untrusted_updates_within_heartbeat_and_deviation() {
+    let env = Env::new().with_feed_metadata(feed_metadata(3_600_000, 10_000));
+    let scenario = scenario_untrusted_updates_twice_waiting_for_threshold(Duration::from_secs(40));
+
+    scenario.run(env);
+}
+
+#[test]
+fn trusted_updates_within_heartbeat_and_deviation() {
+    let env = Env::new().with_feed_metadata(feed_metadata(3_600_000, 10_000));
+    let scenario =
+        scenario_trusted_updates_twice_without_waiting_for_threshold(Duration::from_secs(40));
+
+    scenario.run(env);
+}
+
+#[test]
+#[should_panic(expected = "Error Code: rust-sdk. Error Number: 1102")]
+fn untrusted_updates_within_min_interval_override() {
+    let env = Env::new().with_feed_metadata(FeedMetadata {
+        min_interval_between_updates_ms: 3_600_000,
+        ..Default::default()
+    });
+    let scenario = scenario_untrusted_updates_twice_waiting_for_threshold(Duration::from_secs(40));
+
+    scenario.run(env);
+}
+
+#[test]
+fn precompile_check_initalization() {
+    let env = Env::new().with_precompile_verification();
+    let scenario = scenario_check_initalization();
+
+    scenario.run(env);
+}
+
+#[test]
+#[should_panic(
+    expected = "Error Code: rust-sdk. Error Number: 510. Error Message: Array is empty."
+)]
+fn precompile_updating_with_only_2_signers() {
+    let env = Env::new().with_precompile_verification();
+    let scenario = scenario_updating_with_only_2_signers();
+
+    scenario.run(env);
+}
+
+#[test]
+fn precompile_with_5_signers() {
+    let env = Env::new().with_precompile_verification();
+    let scenario = scenario_with_5_signers(Duration::from_secs(40));
+
+    scenario.run(env);
+}
+
+#[test]
+fn batch_precompile_with_5_signers() {
+    let env = Env::new()
+        .with_batch_writes()
+        .with_precompile_verification();
+    let scenario = scenario_with_5_signers(Duration::from_secs(40));
+
+    scenario.run(env);
+}
+
+#[cfg(test)]
+fn with_5_signers_compute_units(env: Env) -> u64 {
+    let compute_units = env.compute_units();
+    let scenario = scenario_with_5_signers(Duration::from_secs(40));
+
+    scenario.run(env);
+
+    let compute_units = compute_units.borrow();
+    compute_units.iter().sum::<u64>() / compute_units.len() as u64
+}
+
+/// Recovering a signature costs about 25 000 compute units, so verifying the 5 signatures
+/// by the precompile must save at least 2 of them, even with the sysvar parsing.
+#[cfg(test)]
+const MIN_PRECOMPILE_SAVED_COMPUTE_UNITS: u64 = 50_000;
+
+#[test]
+fn precompile_verification_compute_units() {
+    for (name, recovered, precompiled) in [
+        (
+            "write_price",
+            with_5_signers_compute_units(Env::new()),
+            with_5_signers_compute_units(Env::new().with_precompile_verification()),
+        ),
+        (
+            "write_prices",
+            with_5_signers_compute_units(Env::new().with_batch_writes()),
+            with_5_signers_compute_units(
+                Env::new()
+                    .with_batch_writes()
+                    .with_precompile_verification(),
+            ),
+        ),
+    ] {
+        assert!(
+            precompiled + MIN_PRECOMPILE_SAVED_COMPUTE_UNITS <= recovered,
+            "{name}: {recovered} CU recovered, {precompiled} CU with precompile"
+        );
+    }
+}
+
+#[test]
+fn buffered_check_initalization() {
+    let env = Env::new().with_buffered_writes();
+    let scenario = scenario_check_initalization();
+
+    scenario.run(env);
+}
+
+#[test]
+fn buffered_with_5_signers() {
+    let env = Env::new().with_buffered_writes().with_price_history();
+    let scenario = scenario_with_5_signers(Duration::from_secs(40));
+
+    scenario.run(env);
+}
+
+#[test]
+#[should_panic(expected = "Error Code: rust-sdk. Error Number: 1102")]
+fn buffered_untrusted_updates_twice_without_waiting_for_threshold() {
+    let env = Env::new().with_buffered_writes();
+    let scenario =
+        scenario_untrusted_updates_twice_without_waiting_for_threshold(Duration::from_secs(40));
+
+    scenario.run(env);
+}
+
+#[test]
+fn buffered_trusted_updates_twice_without_waiting_for_threshold() {
+    let env = Env::new().with_buffered_writes();
+    let scenario =
+        scenario_trusted_updates_twice_without_waiting_for_threshold(Duration::from_secs(40));
+
+    scenario.run(env);
+}
+
+#[test]
+fn close_payload_buffer() {
+    let mut env = Env::new();
+    let payload = vec![7; 2 * PAYLOAD_CHUNK_SIZE + 1];
+    env.upload_payload(3, &payload, Actor::Untrusted);
+
+    let buffer = env.payload_buffer(3, Actor::Untrusted).unwrap();
+    assert_eq!(&buffer.data[12..], &payload[..]);
+
+    env.close_payload_buffer(3, Actor::Untrusted);
+
+    assert!(env.payload_buffer(3, Actor::Untrusted).is_none());
+}
+
+#[test]
+#[should_panic(expected = "Error Code: PayloadChunkOutOfOrder")]
+fn append_payload_chunk_out_of_order() {
+    let mut env = Env::new();
+    env.init_payload_buffer(0, 100, Actor::Untrusted);
+
+    env.append_payload_chunk(0, 10, &[1; 10], Actor::Untrusted);
+}
+
+#[test]
+#[should_panic(expected = "Error Code: PayloadBufferOverflow")]
+fn append_payload_chunk_over_capacity() {
+    let mut env = Env::new();
+    env.init_payload_buffer(0, 100, Actor::Untrusted);
+    env.append_payload_chunk(0, 0, &[1; 60], Actor::Untrusted);
+
+    env.append_payload_chunk(0, 60, &[1; 60], Actor::Untrusted);
+}
+
+#[test]
+#[should_panic(expected = "Error Code: PayloadBufferTooLarge")]
+fn init_payload_buffer_too_large() {
+    let mut env = Env::new();
+
+    env.init_payload_buffer(0, 10_001, Actor::Untrusted);
+}
+
+#[test]
+#[should_panic(expected = "Error Code: PayloadHashMismatch")]
+fn write_prices_from_buffer_with_wrong_hash() {
+    let mut env = Env::new();
+    env.upload_payload(0, &[7; 100], Actor::Untrusted);
+
+    env.write_prices_from_buffer(0, vec![b"ETH".to_vec()], [0; 32], Actor::Untrusted);
+}
+
+#[test]
+#[should_panic(expected = "Error Code: Paused")]
+fn paused_write() {
+    let mut env = Env::new();
+    env.set_paused(true, Actor::Trusted);
+    let scenario = scenario_check_initalization();
+
+    scenario.run(env);
+}
+
+#[test]
+#[should_panic(expected = "Error Code: Paused")]
+fn batch_paused_write() {
+    let mut env = Env::new().with_batch_writes();
+    env.set_paused(true, Actor::Trusted);
+    let scenario = scenario_check_initalization();
+
+    scenario.run(env);
+}
+
+#[test]
+fn unpaused_write() {
+    let mut env = Env::new();
+    env.set_paused(true, Actor::Trusted);
+    env.set_paused(false, Actor::Trusted);
+    let scenario = scenario_check_initalization();
+
+    scenario.run(env);
+}
+
+#[test]
+#[should_panic(expected = "Error Code: NotPauser")]
+fn set_paused_by_not_pauser() {
+    let mut env = Env::new();
+
+    env.set_paused(true, Actor::Untrusted);
+}
+
+#[test]
+fn set_paused_by_pauser() {
+    let mut env = Env::new();
+    let pauser = env.actor_pubkey(Actor::Untrusted);
+    env.set_pauser(pauser, Actor::Trusted);
+
+    env.set_paused(true, Actor::Untrusted);
+
+    let config = env.read_config();
+    assert_eq!(config.pauser.to_bytes(), pauser.to_bytes());
+    assert!(config.paused);
+}
+
+#[test]
+#[should_panic(expected = "Error Code: NotAdmin")]
+fn set_pauser_by_not_admin() {
+    let mut env = Env::new();
+    let pauser = env.actor_pubkey(Actor::Untrusted);
+
+    env.set_pauser(pauser, Actor::Untrusted);
+}
+
+#[test]
+#[should_panic(expected = "Error Code: FeedPaused")]
+fn paused_feed_write() {
+    let env = Env::new().with_feeds_paused();
+    let scenario = scenario_check_initalization();
+
+    scenario.run(env);
+}
+
+#[test]
+#[should_panic(expected = "Error Code: NotPauser")]
+fn set_feed_paused_by_not_pauser() {
+    let mut env = Env::new();
+    env.set_price_data(
+        b"ETH".to_vec(),
+        u256_value(234_567_000_000),
+        1_744_000_000_000,
+    );
+
+    env.set_feed_paused(b"ETH".to_vec(), true, Actor::Untrusted);
+}
+
+#[test]
+#[should_panic(expected = "Error Code: UnauthorizedUpdater")]
+fn updater_not_in_allow_list() {
+    let mut env = Env::new();
+    env.set_updater_allow_list_enabled(true, Actor::Trusted);
+    let scenario = scenario_untrusted_updates_twice_waiting_for_threshold(Duration::from_secs(40));
+
+    scenario.run(env);
+}
+
+#[test]
+fn updater_in_allow_list() {
+    let mut env = Env::new();
+    let updater = env.actor_pubkey(Actor::Untrusted);
+    env.set_updater_allow_list_enabled(true, Actor::Trusted);
+    env.set_updater_allowed(updater, true, Actor::Trusted);
+    let scenario = scenario_untrusted_updates_twice_waiting_for_threshold(Duration::from_secs(40));
+
+    scenario.run(env);
+}
+
+#[test]
+fn trusted_updater_with_allow_list() {
+    let mut env = Env::new();
+    env.set_updater_allow_list_enabled(true, Actor::Trusted);
+    let scenario =
+        scenario_trusted_updates_twice_without_waiting_for_threshold(Duration::from_secs(40));
+
+    scenario.run(env);
+}
+
+#[test]
+#[should_panic(expected = "Error Code: NotAdmin")]
+fn set_updater_allowed_by_not_admin() {
+    let mut env = Env::new();
+    let updater = env.actor_pubkey(Actor::Untrusted);
+
+    env.set_updater_allowed(updater, true, Actor::Untrusted);
+}
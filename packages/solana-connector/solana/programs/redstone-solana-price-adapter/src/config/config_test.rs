@@ -1,7 +1,7 @@
 use anchor_lang::prelude::Pubkey;
 use hex_literal::hex;
 
-pub type SignerAddressBs = [u8; 20];
+use crate::state::SignerAddressBs;
 
 pub const SIGNER_COUNT: usize = 20;
 pub const UPDATER_COUNT: usize = 1;
@@ -32,6 +32,8 @@ pub const REDSTONE_PRIMARY_PROD_ALLOWED_SIGNERS: [SignerAddressBs; SIGNER_COUNT]
     hex!("8626f6940E2eb28930eFb4CeF49B2d1F2C9C1199"),
 ];
 
-pub const TRUSTED_UPDATERS: [Pubkey; 1] = [Pubkey::new_from_array(hex!(
+pub const TRUSTED_UPDATERS: [Pubkey; UPDATER_COUNT] = [Pubkey::new_from_array(hex!(
     "f7a8654c99499d762eccafd584e8b16ab5119c162611f7c99f70d2d781fb3931"
 ))];
+
+pub const INITIAL_ADMIN: Pubkey = TRUSTED_UPDATERS[0];
@@ -1,10 +1,20 @@
 use anchor_lang::prelude::*;
 
 use redstone::{
+    contract::verification::verify_signers_config,
     solana::{SolanaCrypto, SolanaEnv},
     ConfigFactory, SignerAddress,
 };
 
+use crate::{
+    crypto::PrecompileCrypto,
+    error::PriceAdapterError,
+    state::{
+        ConfigAccount, ConfigParams, FeedIdBs, SignerAddressBs, MAX_ALLOWED_FEED_COUNT,
+        MAX_SIGNER_COUNT, MAX_TRUSTED_UPDATER_COUNT, MAX_UPDATER_COUNT,
+    },
+};
+
 #[cfg(not(feature = "agnostic-tests"))]
 mod config_prod;
 #[cfg(feature = "agnostic-tests")]
@@ -16,10 +26,13 @@ use config_prod as config_values;
 use config_test as config_values;
 
 use config_values::{
-    SignerAddressBs, MAX_TIMESTAMP_AHEAD_MS, MAX_TIMESTAMP_DELAY_MS,
-    REDSTONE_PRIMARY_PROD_ALLOWED_SIGNERS, SIGNER_COUNT, TRUSTED_UPDATERS, UPDATER_COUNT,
+    MAX_TIMESTAMP_AHEAD_MS, MAX_TIMESTAMP_DELAY_MS, REDSTONE_PRIMARY_PROD_ALLOWED_SIGNERS,
+    SIGNER_COUNT, TRUSTED_UPDATERS, UPDATER_COUNT,
 };
 
+pub use config_values::INITIAL_ADMIN;
+
+/// Values the [`ConfigAccount`] is initialized with, later changeable by its admin.
 pub struct Config {
     pub signer_count_threshold: u8,
     pub signers: [SignerAddressBs; SIGNER_COUNT],
@@ -64,13 +77,148 @@ impl Config {
     pub fn trusted_updaters(&self) -> &[Pubkey] {
         &self.trusted_updaters
     }
+
+    pub fn params(&self) -> ConfigParams {
+        ConfigParams {
+            signer_count_threshold: self.signer_count_threshold,
+            signers: self.signers.to_vec(),
+            trusted_updaters: self.trusted_updaters.to_vec(),
+            max_timestamp_delay_ms: self.max_timestamp_delay_ms,
+            max_timestamp_ahead_ms: self.max_timestamp_ahead_ms,
+            min_interval_between_updates_ms: self.min_interval_between_updates_ms,
+        }
+    }
+}
+
+impl ConfigFactory<(), SolanaCrypto> for ConfigAccount {
+    fn signer_count_threshold(&self) -> u8 {
+        self.signer_count_threshold
+    }
+
+    fn redstone_signers(&self) -> Vec<SignerAddress> {
+        self.signers.iter().map(|s| s.to_vec().into()).collect()
+    }
+
+    fn max_timestamp_delay_ms(&self) -> u64 {
+        self.max_timestamp_delay_ms
+    }
+
+    fn max_timestamp_ahead_ms(&self) -> u64 {
+        self.max_timestamp_ahead_ms
+    }
+
+    fn make_crypto(_: ()) -> SolanaCrypto {
+        SolanaCrypto
+    }
+}
+
+/// The [`PrecompileCrypto`] is loaded by the instruction, so it's the argument of the factory.
+impl ConfigFactory<PrecompileCrypto, PrecompileCrypto> for ConfigAccount {
+    fn signer_count_threshold(&self) -> u8 {
+        self.signer_count_threshold
+    }
+
+    fn redstone_signers(&self) -> Vec<SignerAddress> {
+        self.signers.iter().map(|s| s.to_vec().into()).collect()
+    }
+
+    fn max_timestamp_delay_ms(&self) -> u64 {
+        self.max_timestamp_delay_ms
+    }
+
+    fn max_timestamp_ahead_ms(&self) -> u64 {
+        self.max_timestamp_ahead_ms
+    }
+
+    fn make_crypto(crypto: PrecompileCrypto) -> PrecompileCrypto {
+        crypto
+    }
+}
+
+impl ConfigAccount {
+    pub fn set_params(&mut self, params: ConfigParams) -> Result<()> {
+        require!(
+            params.signers.len() <= MAX_SIGNER_COUNT,
+            PriceAdapterError::TooManySigners
+        );
+        require!(
+            params.trusted_updaters.len() <= MAX_TRUSTED_UPDATER_COUNT,
+            PriceAdapterError::TooManyTrustedUpdaters
+        );
+
+        self.signer_count_threshold = params.signer_count_threshold;
+        self.signers = params.signers;
+        self.trusted_updaters = params.trusted_updaters;
+        self.max_timestamp_delay_ms = params.max_timestamp_delay_ms;
+        self.max_timestamp_ahead_ms = params.max_timestamp_ahead_ms;
+        self.min_interval_between_updates_ms = params.min_interval_between_updates_ms;
+
+        self.verify()
+    }
+
+    pub fn is_feed_allowed(&self, feed_id: &FeedIdBs) -> bool {
+        self.allowed_feed_ids.contains(feed_id)
+    }
+
+    pub fn set_feed_allowed(&mut self, feed_id: FeedIdBs, allowed: bool) -> Result<()> {
+        self.allowed_feed_ids
+            .retain(|allowed_feed_id| *allowed_feed_id != feed_id);
+
+        if allowed {
+            require!(
+                self.allowed_feed_ids.len() < MAX_ALLOWED_FEED_COUNT,
+                PriceAdapterError::TooManyAllowedFeeds
+            );
+            self.allowed_feed_ids.push(feed_id);
+        }
+
+        Ok(())
+    }
+
+    pub fn set_updater_allowed(&mut self, updater: Pubkey, allowed: bool) -> Result<()> {
+        self.updaters
+            .retain(|allowed_updater| *allowed_updater != updater);
+
+        if allowed {
+            require!(
+                self.updaters.len() < MAX_UPDATER_COUNT,
+                PriceAdapterError::TooManyUpdaters
+            );
+            self.updaters.push(updater);
+        }
+
+        Ok(())
+    }
+
+    pub fn is_pauser(&self, authority: &Pubkey) -> bool {
+        *authority == self.pauser || *authority == self.admin
+    }
+
+    /// Checks whether the writes aren't paused and the `updater` is allowed to write.
+    pub fn verify_updater(&self, updater: &Pubkey) -> Result<()> {
+        require!(!self.paused, PriceAdapterError::Paused);
+
+        if self.updater_allow_list_enabled {
+            require!(
+                self.updaters.contains(updater) || self.trusted_updaters.contains(updater),
+                PriceAdapterError::UnauthorizedUpdater
+            );
+        }
+
+        Ok(())
+    }
+
+    pub fn verify(&self) -> Result<()> {
+        verify_signers_config(&self.redstone_signers(), self.signer_count_threshold)?;
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::config::SOLANA_CONFIG;
-    use redstone::contract::verification::verify_signers_config;
 
     #[test]
     fn verify_integrity_of_the_config() {
@@ -0,0 +1,149 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    instruction::Instruction,
+    secp256k1_program,
+    sysvar::instructions::{self, load_instruction_at_checked},
+};
+use redstone::{solana::SolanaCrypto, Bytes, Crypto, CryptoError, SignerAddress};
+
+use crate::{error::PriceAdapterError, state::SignerAddressBs};
+
+const SIGNATURE_OFFSETS_SIZE: usize = 11;
+const SIGNATURE_SIZE: usize = 64;
+const ETH_ADDRESS_SIZE: usize = 20;
+const ETH_RECOVERY_ID_OFFSET: u8 = 27;
+
+/// A signature checked by the secp256k1 precompile before the program was invoked.
+struct VerifiedSignature {
+    signer: SignerAddressBs,
+    signature: [u8; SIGNATURE_SIZE],
+    recovery_id: u8,
+    message: Vec<u8>,
+}
+
+impl VerifiedSignature {
+    fn matches(&self, message: &[u8], signature: &[u8]) -> bool {
+        let Some((&recovery_byte, signature)) = signature.split_last() else {
+            return false;
+        };
+        let recovery_id = recovery_byte
+            .checked_sub(ETH_RECOVERY_ID_OFFSET)
+            .unwrap_or(recovery_byte);
+
+        self.recovery_id == recovery_id && self.signature == signature && self.message == message
+    }
+}
+
+/// [`SolanaCrypto`] taking the signers of the data packages from the secp256k1 precompile
+/// instructions of the transaction instead of recovering them, which saves the compute units
+/// of the `secp256k1_recover` syscall for each signature.
+/// The signatures not verified by any precompile instruction are still recovered by the program.
+pub struct PrecompileCrypto {
+    signatures: Vec<VerifiedSignature>,
+    inner: SolanaCrypto,
+}
+
+impl PrecompileCrypto {
+    pub fn load(instructions_sysvar: &AccountInfo) -> Result<Self> {
+        let mut transaction_instructions = vec![];
+        while let Ok(instruction) =
+            load_instruction_at_checked(transaction_instructions.len(), instructions_sysvar)
+        {
+            transaction_instructions.push(instruction);
+        }
+
+        let mut signatures = vec![];
+        for instruction in transaction_instructions
+            .iter()
+            .filter(|instruction| instruction.program_id == secp256k1_program::ID)
+        {
+            let count = usize::from(instruction.data.first().copied().unwrap_or_default());
+            let offsets = instruction
+                .data
+                .get(1..1 + count * SIGNATURE_OFFSETS_SIZE)
+                .ok_or(PriceAdapterError::InvalidPrecompileInstruction)?;
+
+            for offsets in offsets.chunks_exact(SIGNATURE_OFFSETS_SIZE) {
+                signatures.push(read_signature(&transaction_instructions, offsets)?);
+            }
+        }
+
+        Ok(Self {
+            signatures,
+            inner: SolanaCrypto,
+        })
+    }
+}
+
+/// Reads the signature described by the `SecpSignatureOffsets` of the precompile instruction.
+fn read_signature(instructions: &[Instruction], offsets: &[u8]) -> Result<VerifiedSignature> {
+    let read_u16 = |at: usize| u16::from_le_bytes([offsets[at], offsets[at + 1]]);
+
+    let signature = slice_at(instructions, offsets[2], read_u16(0), SIGNATURE_SIZE + 1)?;
+    let signer = slice_at(instructions, offsets[5], read_u16(3), ETH_ADDRESS_SIZE)?;
+    let message = slice_at(instructions, offsets[10], read_u16(6), read_u16(8).into())?;
+
+    Ok(VerifiedSignature {
+        signer: signer.try_into().unwrap(),
+        signature: signature[..SIGNATURE_SIZE].try_into().unwrap(),
+        recovery_id: signature[SIGNATURE_SIZE],
+        message: message.to_vec(),
+    })
+}
+
+fn slice_at(instructions: &[Instruction], index: u8, offset: u16, len: usize) -> Result<&[u8]> {
+    let offset = usize::from(offset);
+
+    instructions
+        .get(usize::from(index))
+        .and_then(|instruction| instruction.data.get(offset..offset + len))
+        .ok_or_else(|| PriceAdapterError::InvalidPrecompileInstruction.into())
+}
+
+impl Crypto for PrecompileCrypto {
+    type KeccakOutput = <SolanaCrypto as Crypto>::KeccakOutput;
+
+    fn keccak256(&mut self, input: impl AsRef<[u8]>) -> Self::KeccakOutput {
+        self.inner.keccak256(input)
+    }
+
+    fn recover_public_key(
+        &mut self,
+        recovery_byte: u8,
+        signature_bytes: impl AsRef<[u8]>,
+        message_hash: Self::KeccakOutput,
+    ) -> std::result::Result<Bytes, CryptoError> {
+        self.inner
+            .recover_public_key(recovery_byte, signature_bytes, message_hash)
+    }
+
+    fn recover_address<A: AsRef<[u8]>, B: AsRef<[u8]>>(
+        &mut self,
+        message: A,
+        signature: B,
+    ) -> std::result::Result<SignerAddress, CryptoError> {
+        let verified = self
+            .signatures
+            .iter()
+            .find(|verified| verified.matches(message.as_ref(), signature.as_ref()));
+
+        match verified {
+            Some(verified) => Ok(verified.signer.to_vec().into()),
+            None => self.inner.recover_address(message, signature),
+        }
+    }
+}
+
+/// Splits off the instructions sysvar passed as the last of the `remaining_accounts`,
+/// which makes the write instructions use the [`PrecompileCrypto`].
+pub(crate) fn split_instructions_sysvar<'info>(
+    accounts: &'info [AccountInfo<'info>],
+) -> (
+    &'info [AccountInfo<'info>],
+    Option<&'info AccountInfo<'info>>,
+) {
+    match accounts.split_last() {
+        Some((last, rest)) if last.key() == instructions::ID => (rest, Some(last)),
+        _ => (accounts, None),
+    }
+}
@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum PriceAdapterError {
+    #[msg("Number of price accounts doesn't match the number of feed ids")]
+    PriceAccountCountMismatch,
+    #[msg("Price account address doesn't match the feed id")]
+    InvalidPriceAccount,
+    #[msg("Price account must be writable")]
+    PriceAccountNotWritable,
+    #[msg("Feed value is missing in the payload")]
+    MissingFeedValue,
+    #[msg("Signer is not the admin")]
+    NotAdmin,
+    #[msg("Signer is not the pending admin")]
+    NotPendingAdmin,
+    #[msg("Too many signers")]
+    TooManySigners,
+    #[msg("Too many trusted updaters")]
+    TooManyTrustedUpdaters,
+    #[msg("Price doesn't fit in i64")]
+    PriceOverflow,
+    #[msg("Price has never been written")]
+    PriceNotInitialized,
+    #[msg("Price is older than the allowed max age")]
+    StalePrice,
+    #[msg("Too many price history entries requested")]
+    TooManyHistoryEntries,
+    #[msg("Price history account doesn't match the feed id")]
+    InvalidPriceHistoryAccount,
+    #[msg("Feed is not allowed to be initialized")]
+    FeedNotAllowed,
+    #[msg("Too many allowed feeds")]
+    TooManyAllowedFeeds,
+    #[msg("Account is not a price account")]
+    InvalidPriceAccountData,
+    #[msg("Price is neither past the heartbeat nor beyond the deviation threshold")]
+    UpdateNotRequired,
+    #[msg("Secp256k1 precompile instruction points outside of the transaction data")]
+    InvalidPrecompileInstruction,
+    #[msg("Payload buffer capacity is too large")]
+    PayloadBufferTooLarge,
+    #[msg("Payload chunk doesn't start at the end of the buffered data")]
+    PayloadChunkOutOfOrder,
+    #[msg("Payload chunk exceeds the buffer capacity")]
+    PayloadBufferOverflow,
+    #[msg("Buffered payload doesn't match the hash")]
+    PayloadHashMismatch,
+    #[msg("Signer is neither the pauser nor the admin")]
+    NotPauser,
+    #[msg("Writes are paused")]
+    Paused,
+    #[msg("Writes of the feed are paused")]
+    FeedPaused,
+    #[msg("Updater is not allowed to write")]
+    UnauthorizedUpdater,
+    #[msg("Too many updaters")]
+    TooManyUpdaters,
+    #[msg("Derived price formula has no components or too many of them")]
+    InvalidDerivedPriceFormula,
+    #[msg("Derived price formula divides by a zero price")]
+    DerivedPriceDivisionByZero,
+    #[msg("Feed id is passed more than once")]
+    DuplicateFeedId,
+    #[msg("Scaled price account doesn't match the feed id")]
+    InvalidScaledPriceAccount,
+    #[msg("Price was written earlier than the allowed max age")]
+    StalePriceWrite,
+}
@@ -0,0 +1,70 @@
+use crate::state::{FeedIdBs, PriceData, ValueBs};
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct PriceUpdated {
+    pub feed_id: FeedIdBs,
+    pub value: ValueBs,
+    pub timestamp: u64,
+    pub write_slot: u64,
+    pub updater: Pubkey,
+}
+
+impl PriceUpdated {
+    pub fn new(price_data: &PriceData, updater: Pubkey) -> Self {
+        Self {
+            feed_id: price_data.feed_id,
+            value: price_data.value,
+            timestamp: price_data.timestamp,
+            write_slot: price_data.write_slot_number,
+            updater,
+        }
+    }
+}
+
+/// Emitted for every feed skipped by the batch writes, with the `error_code`
+/// the same update would fail with when written alone.
+#[event]
+pub struct PriceUpdateRejected {
+    pub feed_id: FeedIdBs,
+    pub package_timestamp: u64,
+    pub updater: Pubkey,
+    pub error_code: u32,
+}
+
+impl PriceUpdateRejected {
+    pub fn new(feed_id: FeedIdBs, package_timestamp: u64, updater: Pubkey, error: Error) -> Self {
+        Self {
+            feed_id,
+            package_timestamp,
+            updater,
+            error_code: error_code(&error),
+        }
+    }
+}
+
+/// Emits the event by a self-CPI when the `event-cpi` feature is enabled,
+/// so it can't be truncated from the logs, otherwise by logging it.
+macro_rules! emit_event {
+    ($ctx:ident, $event:expr) => {{
+        #[cfg(feature = "event-cpi")]
+        {
+            let ctx = &$ctx;
+            anchor_lang::prelude::emit_cpi!($event);
+        }
+        #[cfg(not(feature = "event-cpi"))]
+        anchor_lang::prelude::emit!($event);
+    }};
+}
+
+pub(crate) use emit_event;
+
+fn error_code(error: &Error) -> u32 {
+    match error {
+        Error::AnchorError(error) => error.error_code_number,
+        Error::ProgramError(error) => match error.program_error {
+            ProgramError::Custom(code) => code,
+            _ => u32::MAX,
+        },
+    }
+}
@@ -0,0 +1,126 @@
+use crate::config::{INITIAL_ADMIN, SOLANA_CONFIG};
+use crate::{
+    error::PriceAdapterError,
+    state::{ConfigAccount, ConfigParams, CONFIG_ACCOUNT_SPACE},
+    util::make_config_seed,
+    FeedIdBs,
+};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = CONFIG_ACCOUNT_SPACE,
+        seeds = [&make_config_seed()],
+        bump
+    )]
+    pub config_account: Account<'info, ConfigAccount>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [&make_config_seed()],
+        bump,
+        has_one = admin @ PriceAdapterError::NotAdmin
+    )]
+    pub config_account: Account<'info, ConfigAccount>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info> {
+    pub pending_admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [&make_config_seed()],
+        bump,
+        constraint = config_account.pending_admin == Some(pending_admin.key())
+            @ PriceAdapterError::NotPendingAdmin
+    )]
+    pub config_account: Account<'info, ConfigAccount>,
+}
+
+#[derive(Accounts)]
+pub struct ReadConfig<'info> {
+    #[account(seeds = [&make_config_seed()], bump)]
+    pub config_account: Account<'info, ConfigAccount>,
+}
+
+/// Creates the config account with the values compiled into the program.
+/// The admin is always the `INITIAL_ADMIN`, so it doesn't matter who pays for the account.
+pub fn initialize_config(ctx: Context<InitializeConfig>) -> Result<()> {
+    let config_account = &mut ctx.accounts.config_account;
+
+    config_account.admin = INITIAL_ADMIN;
+    config_account.pending_admin = None;
+    config_account.allowed_feed_ids = vec![];
+    config_account.pauser = INITIAL_ADMIN;
+    config_account.paused = false;
+    config_account.updater_allow_list_enabled = false;
+    config_account.updaters = vec![];
+
+    config_account.set_params(SOLANA_CONFIG.params())
+}
+
+pub fn update_config(ctx: Context<UpdateConfig>, params: ConfigParams) -> Result<()> {
+    ctx.accounts.config_account.set_params(params)
+}
+
+pub fn set_feed_allowed(
+    ctx: Context<UpdateConfig>,
+    feed_id: FeedIdBs,
+    allowed: bool,
+) -> Result<()> {
+    ctx.accounts
+        .config_account
+        .set_feed_allowed(feed_id, allowed)
+}
+
+pub fn set_pauser(ctx: Context<UpdateConfig>, pauser: Pubkey) -> Result<()> {
+    ctx.accounts.config_account.pauser = pauser;
+
+    Ok(())
+}
+
+pub fn set_updater_allowed(
+    ctx: Context<UpdateConfig>,
+    updater: Pubkey,
+    allowed: bool,
+) -> Result<()> {
+    ctx.accounts
+        .config_account
+        .set_updater_allowed(updater, allowed)
+}
+
+/// When enabled, only the allowed `updaters` and the `trusted_updaters` can write the prices.
+pub fn set_updater_allow_list_enabled(ctx: Context<UpdateConfig>, enabled: bool) -> Result<()> {
+    ctx.accounts.config_account.updater_allow_list_enabled = enabled;
+
+    Ok(())
+}
+
+/// The first step of the admin transfer, finished by [`accept_admin`] signed by the `new_admin`.
+pub fn transfer_admin(ctx: Context<UpdateConfig>, new_admin: Pubkey) -> Result<()> {
+    ctx.accounts.config_account.pending_admin = Some(new_admin);
+
+    Ok(())
+}
+
+pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+    let config_account = &mut ctx.accounts.config_account;
+
+    config_account.admin = ctx.accounts.pending_admin.key();
+    config_account.pending_admin = None;
+
+    Ok(())
+}
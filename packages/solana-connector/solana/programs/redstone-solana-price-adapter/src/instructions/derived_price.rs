@@ -0,0 +1,60 @@
+use crate::{
+    error::PriceAdapterError,
+    state::{DerivedPrice, DerivedPriceFormula, FeedIdBs, PriceData},
+    util::make_price_seed,
+};
+use anchor_lang::prelude::*;
+
+/// The `PriceData` accounts of the formula components are passed via
+/// `remaining_accounts`, in the order of the components.
+#[derive(Accounts)]
+pub struct ReadDerivedPrice {}
+
+pub fn read_derived_price(
+    ctx: Context<ReadDerivedPrice>,
+    formula: DerivedPriceFormula,
+) -> Result<DerivedPrice> {
+    require_eq!(
+        ctx.remaining_accounts.len(),
+        formula.components.len(),
+        PriceAdapterError::PriceAccountCountMismatch
+    );
+
+    let prices = formula
+        .components
+        .iter()
+        .zip(ctx.remaining_accounts)
+        .map(|(component, price_account)| {
+            load_price_data(ctx.program_id, &component.feed_id, price_account)
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    formula.compute(&prices)
+}
+
+/// Checks the account the same way as the [`super::ReadPrice`] constraints do.
+fn load_price_data(
+    program_id: &Pubkey,
+    feed_id: &FeedIdBs,
+    price_account: &AccountInfo,
+) -> Result<PriceData> {
+    let (address, _) = Pubkey::find_program_address(&[&make_price_seed(), feed_id], program_id);
+    require_keys_eq!(
+        price_account.key(),
+        address,
+        PriceAdapterError::InvalidPriceAccount
+    );
+    require_keys_eq!(
+        *price_account.owner,
+        *program_id,
+        ErrorCode::AccountOwnedByWrongProgram
+    );
+
+    let price_data = PriceData::try_deserialize(&mut &price_account.try_borrow_data()?[..])?;
+    require!(
+        price_data.write_timestamp.is_some() && price_data.timestamp > 0,
+        PriceAdapterError::PriceNotInitialized
+    );
+
+    Ok(price_data)
+}
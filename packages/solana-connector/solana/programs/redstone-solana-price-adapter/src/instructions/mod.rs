@@ -1,6 +1,22 @@
+pub mod config;
+pub mod derived_price;
+pub mod pause;
+pub mod payload_buffer;
+pub mod price_account;
+pub mod price_history;
 pub mod read_price_data;
+pub mod scaled_price;
 pub mod write_price;
+pub mod write_prices;
 
+pub use config::*;
 pub use write_price::*;
+pub use write_prices::*;
 
+pub use derived_price::*;
+pub use pause::*;
+pub use payload_buffer::*;
+pub use price_account::*;
+pub use price_history::*;
 pub use read_price_data::*;
+pub use scaled_price::*;
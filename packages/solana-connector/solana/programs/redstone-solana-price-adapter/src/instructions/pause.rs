@@ -0,0 +1,58 @@
+use crate::{
+    error::PriceAdapterError,
+    state::{ConfigAccount, PriceData},
+    util::{make_config_seed, make_price_seed},
+    FeedIdBs,
+};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct Pause<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [&make_config_seed()],
+        bump,
+        constraint = config_account.is_pauser(&authority.key()) @ PriceAdapterError::NotPauser
+    )]
+    pub config_account: Account<'info, ConfigAccount>,
+}
+
+#[derive(Accounts)]
+#[instruction(feed_id: FeedIdBs)]
+pub struct PauseFeed<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [&make_config_seed()],
+        bump,
+        constraint = config_account.is_pauser(&authority.key()) @ PriceAdapterError::NotPauser
+    )]
+    pub config_account: Account<'info, ConfigAccount>,
+
+    #[account(
+        mut,
+        seeds = [
+            &make_price_seed(),
+            &feed_id
+        ],
+        bump
+    )]
+    pub price_account: Account<'info, PriceData>,
+}
+
+/// Pauses or unpauses the writes of all feeds, signed by the pauser or the admin.
+pub fn set_paused(ctx: Context<Pause>, paused: bool) -> Result<()> {
+    ctx.accounts.config_account.paused = paused;
+
+    Ok(())
+}
+
+/// Pauses or unpauses the writes of the feed, signed by the pauser or the admin.
+/// The reads of a paused feed still return its last value.
+pub fn set_feed_paused(ctx: Context<PauseFeed>, paused: bool) -> Result<()> {
+    ctx.accounts.price_account.paused = paused;
+
+    Ok(())
+}
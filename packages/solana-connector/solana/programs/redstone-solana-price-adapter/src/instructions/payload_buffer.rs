@@ -0,0 +1,168 @@
+use crate::{
+    error::PriceAdapterError,
+    events::{emit_event, PriceUpdated},
+    instructions::write_prices::{FeedWrite, FeedWriter},
+    state::{payload_buffer_space, ConfigAccount, PayloadBuffer, MAX_PAYLOAD_BUFFER_CAPACITY},
+    util::{make_config_seed, make_payload_buffer_seed},
+    FeedIdBs,
+};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+
+#[derive(Accounts)]
+#[instruction(buffer_id: u64, capacity: u32)]
+pub struct InitPayloadBuffer<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        init,
+        payer = user,
+        space = payload_buffer_space(capacity as usize),
+        seeds = [
+            &make_payload_buffer_seed(),
+            user.key().as_ref(),
+            &buffer_id.to_le_bytes()
+        ],
+        bump
+    )]
+    pub payload_buffer: Account<'info, PayloadBuffer>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(buffer_id: u64)]
+pub struct AppendPayloadChunk<'info> {
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            &make_payload_buffer_seed(),
+            user.key().as_ref(),
+            &buffer_id.to_le_bytes()
+        ],
+        bump
+    )]
+    pub payload_buffer: Account<'info, PayloadBuffer>,
+}
+
+#[derive(Accounts)]
+#[instruction(buffer_id: u64)]
+pub struct ClosePayloadBuffer<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        close = user,
+        seeds = [
+            &make_payload_buffer_seed(),
+            user.key().as_ref(),
+            &buffer_id.to_le_bytes()
+        ],
+        bump
+    )]
+    pub payload_buffer: Account<'info, PayloadBuffer>,
+}
+
+/// The accounts of [`WritePrices`](crate::instructions::WritePrices) with the `payload_buffer`,
+/// closed after the write.
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+#[instruction(buffer_id: u64)]
+pub struct WritePricesFromBuffer<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(seeds = [&make_config_seed()], bump)]
+    pub config_account: Account<'info, ConfigAccount>,
+
+    #[account(
+        mut,
+        close = user,
+        seeds = [
+            &make_payload_buffer_seed(),
+            user.key().as_ref(),
+            &buffer_id.to_le_bytes()
+        ],
+        bump
+    )]
+    pub payload_buffer: Account<'info, PayloadBuffer>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Creates the buffer of the `user` for a payload of up to `capacity` bytes.
+/// The `buffer_id` lets a relayer fill several buffers at the same time.
+pub fn init_payload_buffer(ctx: Context<InitPayloadBuffer>, capacity: u32) -> Result<()> {
+    require!(
+        capacity as usize <= MAX_PAYLOAD_BUFFER_CAPACITY,
+        PriceAdapterError::PayloadBufferTooLarge
+    );
+
+    ctx.accounts.payload_buffer.data = vec![];
+
+    Ok(())
+}
+
+/// Appends the `chunk` to the buffered data, which must be `offset` bytes long,
+/// so the chunks can't be reordered or duplicated by the resent transactions.
+pub fn append_payload_chunk(
+    ctx: Context<AppendPayloadChunk>,
+    offset: u32,
+    chunk: Vec<u8>,
+) -> Result<()> {
+    let capacity =
+        ctx.accounts.payload_buffer.to_account_info().data_len() - payload_buffer_space(0);
+    let buffer = &mut ctx.accounts.payload_buffer;
+
+    require!(
+        offset as usize == buffer.data.len(),
+        PriceAdapterError::PayloadChunkOutOfOrder
+    );
+    require!(
+        buffer.data.len() + chunk.len() <= capacity,
+        PriceAdapterError::PayloadBufferOverflow
+    );
+
+    buffer.data.extend(chunk);
+
+    Ok(())
+}
+
+/// Returns the lamports of the buffer not consumed by [`write_prices_from_buffer`] to the `user`.
+pub fn close_payload_buffer(_ctx: Context<ClosePayloadBuffer>) -> Result<()> {
+    Ok(())
+}
+
+/// Works as [`write_prices`](crate::instructions::write_prices) for the buffered payload,
+/// if it matches the sha256 `payload_hash`, and closes the buffer.
+pub fn write_prices_from_buffer<'info>(
+    ctx: Context<'_, '_, 'info, 'info, WritePricesFromBuffer<'info>>,
+    feed_ids: Vec<FeedIdBs>,
+    payload_hash: [u8; 32],
+) -> Result<()> {
+    let payload = std::mem::take(&mut ctx.accounts.payload_buffer.data);
+    require!(
+        hash(&payload).to_bytes() == payload_hash,
+        PriceAdapterError::PayloadHashMismatch
+    );
+
+    let writer = FeedWriter {
+        program_id: ctx.program_id,
+        user: &ctx.accounts.user,
+        config_account: &ctx.accounts.config_account,
+        system_program: &ctx.accounts.system_program,
+    };
+
+    for feed_write in writer.write(ctx.remaining_accounts, &feed_ids, payload)? {
+        match feed_write {
+            FeedWrite::Updated(price_data) => {
+                let event = PriceUpdated::new(&price_data, ctx.accounts.user.key());
+                emit_event!(ctx, event);
+            }
+            FeedWrite::Rejected(event) => emit_event!(ctx, event),
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,147 @@
+use crate::{
+    error::PriceAdapterError,
+    state::{
+        ConfigAccount, FeedMetadata, PriceData, PriceHistory, ScaledPriceData, PRICE_DATA_SPACE,
+    },
+    util::{make_config_seed, make_price_history_seed, make_price_seed, make_scaled_price_seed},
+    FeedIdBs,
+};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(feed_id: FeedIdBs)]
+pub struct ClosePriceAccount<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [&make_config_seed()],
+        bump,
+        has_one = admin @ PriceAdapterError::NotAdmin
+    )]
+    pub config_account: Account<'info, ConfigAccount>,
+
+    #[account(
+        mut,
+        close = receiver,
+        seeds = [
+            &make_price_seed(),
+            &feed_id
+        ],
+        bump
+    )]
+    pub price_account: Account<'info, PriceData>,
+
+    #[account(mut)]
+    pub receiver: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        close = receiver,
+        seeds = [
+            &make_price_history_seed(),
+            &feed_id
+        ],
+        bump
+    )]
+    pub price_history: Option<AccountLoader<'info, PriceHistory>>,
+
+    #[account(
+        mut,
+        close = receiver,
+        seeds = [
+            &make_scaled_price_seed(),
+            &feed_id
+        ],
+        bump
+    )]
+    pub scaled_price_account: Option<Account<'info, ScaledPriceData>>,
+}
+
+#[derive(Accounts)]
+#[instruction(feed_id: FeedIdBs)]
+pub struct SetFeedMetadata<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [&make_config_seed()],
+        bump,
+        has_one = admin @ PriceAdapterError::NotAdmin
+    )]
+    pub config_account: Account<'info, ConfigAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = PRICE_DATA_SPACE,
+        seeds = [
+            &make_price_seed(),
+            &feed_id
+        ],
+        bump
+    )]
+    pub price_account: Account<'info, PriceData>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(feed_id: FeedIdBs)]
+pub struct ReallocPriceAccount<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: the account can't be deserialized as `PriceData` before it's resized,
+    /// so its discriminator is checked in the instruction
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [
+            &make_price_seed(),
+            &feed_id
+        ],
+        bump,
+        realloc = PRICE_DATA_SPACE,
+        realloc::payer = payer,
+        realloc::zero = false
+    )]
+    pub price_account: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Returns the lamports of the account, as well as of the optional `PriceHistory`
+/// and `ScaledPriceData` accounts of the feed, to the `receiver`.
+/// Unless the feed is also removed from the allowed feeds, the account can be created again by the next write.
+pub fn close_price_account(_ctx: Context<ClosePriceAccount>) -> Result<()> {
+    Ok(())
+}
+
+/// Creates the price account when the feed hasn't been written yet,
+/// so the metadata applies also to its first update.
+pub fn set_feed_metadata(
+    ctx: Context<SetFeedMetadata>,
+    feed_id: FeedIdBs,
+    metadata: FeedMetadata,
+) -> Result<()> {
+    let price_account = &mut ctx.accounts.price_account;
+
+    price_account.metadata = metadata;
+    if price_account.write_timestamp.is_none() {
+        price_account.feed_id = feed_id;
+        price_account.decimals = metadata.decimals();
+    }
+
+    Ok(())
+}
+
+/// Resizes the account created by a former version of the program to the current size of `PriceData`.
+pub fn realloc_price_account(ctx: Context<ReallocPriceAccount>) -> Result<()> {
+    require!(
+        ctx.accounts
+            .price_account
+            .try_borrow_data()?
+            .starts_with(PriceData::DISCRIMINATOR),
+        PriceAdapterError::InvalidPriceAccountData
+    );
+
+    Ok(())
+}
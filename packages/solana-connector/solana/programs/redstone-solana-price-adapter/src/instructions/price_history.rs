@@ -0,0 +1,99 @@
+use crate::{
+    error::PriceAdapterError,
+    state::{
+        HistoricalPrice, PriceData, PriceHistory, PriceHistoryEntry, ValueBs, PRICE_HISTORY_SPACE,
+    },
+    util::{current_time_as_millis, make_price_history_seed},
+    FeedIdBs,
+};
+use anchor_lang::prelude::*;
+
+/// Keeps the returned entries within the 1024 bytes limit of the return data.
+pub const MAX_HISTORY_READ_COUNT: u8 = 20;
+
+#[derive(Accounts)]
+#[instruction(feed_id: FeedIdBs)]
+pub struct InitPriceHistory<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        init,
+        payer = user,
+        space = PRICE_HISTORY_SPACE,
+        seeds = [
+            &make_price_history_seed(),
+            &feed_id
+        ],
+        bump
+    )]
+    pub price_history: AccountLoader<'info, PriceHistory>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(feed_id: FeedIdBs)]
+pub struct ReadPriceHistory<'info> {
+    #[account(
+        seeds = [
+            &make_price_history_seed(),
+            &feed_id
+        ],
+        bump
+    )]
+    pub price_history: AccountLoader<'info, PriceHistory>,
+}
+
+pub fn init_price_history(ctx: Context<InitPriceHistory>, feed_id: FeedIdBs) -> Result<()> {
+    ctx.accounts.price_history.load_init()?.feed_id = feed_id;
+
+    Ok(())
+}
+
+/// Returns up to `count` entries, starting from the newest one.
+pub fn latest_price_history(
+    ctx: Context<ReadPriceHistory>,
+    count: u8,
+) -> Result<Vec<HistoricalPrice>> {
+    require!(
+        count <= MAX_HISTORY_READ_COUNT,
+        PriceAdapterError::TooManyHistoryEntries
+    );
+
+    let price_history = ctx.accounts.price_history.load()?;
+
+    Ok(price_history
+        .latest()
+        .take(count.into())
+        .map(Into::into)
+        .collect())
+}
+
+pub fn price_twap(ctx: Context<ReadPriceHistory>, window_ms: u64) -> Result<ValueBs> {
+    let now = current_time_as_millis()?.as_millis();
+
+    ctx.accounts.price_history.load()?.twap(now, window_ms)
+}
+
+/// Appends the just written `price_data` to the history passed as an optional account
+/// of the write instructions.
+pub(crate) fn record_price_history<'info>(
+    history_account: &'info AccountInfo<'info>,
+    price_data: &PriceData,
+) -> Result<()> {
+    let loader = AccountLoader::<PriceHistory>::try_from(history_account)?;
+    let mut price_history = loader.load_mut()?;
+
+    require!(
+        price_history.feed_id == price_data.feed_id,
+        PriceAdapterError::InvalidPriceHistoryAccount
+    );
+
+    price_history.push(PriceHistoryEntry {
+        value: price_data.value,
+        timestamp: price_data.timestamp,
+        write_slot: price_data.write_slot_number,
+    });
+
+    Ok(())
+}
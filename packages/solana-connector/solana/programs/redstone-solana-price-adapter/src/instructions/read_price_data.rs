@@ -1,6 +1,7 @@
+use crate::error::PriceAdapterError;
 use crate::state::PriceData;
 use crate::state::ValueBs;
-use crate::util::make_price_seed;
+use crate::util::{current_time_as_millis, make_price_seed};
 use crate::FeedIdBs;
 use anchor_lang::prelude::*;
 
@@ -36,3 +37,26 @@ pub fn price(ctx: Context<ReadPrice>) -> ValueBs {
 pub fn timestamp(ctx: Context<ReadPrice>) -> u64 {
     ctx.accounts.price_account.timestamp
 }
+
+/// Returns the value only if it was written and both its package timestamp
+/// and write timestamp are not older than `max_age_ms` in relation to the `Clock`.
+pub fn price_checked(ctx: Context<ReadPrice>, max_age_ms: u64) -> Result<ValueBs> {
+    let data = &ctx.accounts.price_account;
+
+    require!(
+        data.write_timestamp.is_some() && data.timestamp > 0,
+        PriceAdapterError::PriceNotInitialized
+    );
+
+    let now = current_time_as_millis()?.as_millis();
+    require!(
+        now.saturating_sub(data.timestamp) <= max_age_ms,
+        PriceAdapterError::StalePrice
+    );
+    require!(
+        now.saturating_sub(data.write_timestamp.unwrap_or_default()) <= max_age_ms,
+        PriceAdapterError::StalePriceWrite
+    );
+
+    Ok(data.value)
+}
@@ -0,0 +1,65 @@
+use crate::error::PriceAdapterError;
+use crate::instructions::ReadPrice;
+use crate::state::{PriceData, ScaledPrice, ScaledPriceData, SCALED_PRICE_DATA_SPACE};
+use crate::util::{make_price_seed, make_scaled_price_seed};
+use crate::FeedIdBs;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(feed_id: FeedIdBs)]
+pub struct UpdateScaledPrice<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        seeds = [
+            &make_price_seed(),
+            &feed_id
+        ],
+        bump,
+        constraint = price_account.to_account_info().owner == __program_id
+    )]
+    pub price_account: Account<'info, PriceData>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = SCALED_PRICE_DATA_SPACE,
+        seeds = [
+            &make_scaled_price_seed(),
+            &feed_id
+        ],
+        bump
+    )]
+    pub scaled_price_account: Account<'info, ScaledPriceData>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn read_price_scaled(ctx: Context<ReadPrice>) -> Result<ScaledPrice> {
+    ctx.accounts.price_account.scaled_price()
+}
+
+/// Copies the [`PriceData`] of the feed to its [`ScaledPriceData`] mirror.
+/// Anyone can call it, as the mirror holds only the already verified data.
+pub fn update_scaled_price(ctx: Context<UpdateScaledPrice>) -> Result<()> {
+    ctx.accounts
+        .scaled_price_account
+        .update(&ctx.accounts.price_account)
+}
+
+/// Refreshes the mirror passed as an optional account of the write instructions
+/// with the just written `price_data`.
+pub(crate) fn record_scaled_price<'info>(
+    scaled_price_account: &'info AccountInfo<'info>,
+    price_data: &PriceData,
+) -> Result<()> {
+    let mut scaled_price = Account::<ScaledPriceData>::try_from(scaled_price_account)?;
+
+    require!(
+        scaled_price.feed_id == price_data.feed_id,
+        PriceAdapterError::InvalidScaledPriceAccount
+    );
+
+    scaled_price.update(price_data)?;
+    scaled_price.try_serialize(&mut &mut scaled_price_account.try_borrow_mut_data()?[..])
+}
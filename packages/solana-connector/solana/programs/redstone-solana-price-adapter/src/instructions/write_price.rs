@@ -1,7 +1,10 @@
-use crate::config::SOLANA_CONFIG;
 use crate::{
-    state::{PriceData, REDSTONE_DECIMALS_EXP},
-    util::{current_time_as_millis, debug_msg, make_price_seed},
+    crypto::{split_instructions_sysvar, PrecompileCrypto},
+    error::PriceAdapterError,
+    events::{emit_event, PriceUpdated},
+    instructions::{price_history::record_price_history, scaled_price::record_scaled_price},
+    state::{ConfigAccount, PriceData, ScaledPriceData, ValueBs, PRICE_DATA_SPACE},
+    util::{current_time_as_millis, debug_msg, make_config_seed, make_price_seed},
     FeedIdBs,
 };
 use anchor_lang::prelude::*;
@@ -9,9 +12,10 @@ use redstone::FeedValue;
 use redstone::{
     contract::verification::UpdateTimestampVerifier, core::processor::process_payload,
     network::as_str::AsHexStr, network::error::Error as RedStoneError,
-    solana::SolanaRedStoneConfig, ConfigFactory,
+    solana::SolanaRedStoneConfig, ConfigFactory, TimestampMillis,
 };
 
+#[cfg_attr(feature = "event-cpi", event_cpi)]
 #[derive(Accounts)]
 #[instruction(feed_id: FeedIdBs)]
 pub struct WritePrice<'info> {
@@ -21,7 +25,7 @@ pub struct WritePrice<'info> {
     #[account(
         init_if_needed,
         payer = user,
-        space = 8 + std::mem::size_of::<PriceData>(),
+        space = PRICE_DATA_SPACE,
         seeds = [
             &make_price_seed(),
             &feed_id
@@ -30,17 +34,58 @@ pub struct WritePrice<'info> {
         constraint = price_account.to_account_info().owner == __program_id
     )]
     pub price_account: Account<'info, PriceData>,
+    #[account(seeds = [&make_config_seed()], bump)]
+    pub config_account: Account<'info, ConfigAccount>,
     pub system_program: Program<'info, System>,
 }
 
-pub fn write_price(ctx: Context<WritePrice>, feed_id: FeedIdBs, payload: Vec<u8>) -> Result<()> {
+/// The `PriceHistory` and the `ScaledPriceData` accounts of the feed can be passed,
+/// in any order, as the first two of `remaining_accounts` to append the written value to the history
+/// and to refresh the scaled mirror.
+/// The instructions sysvar passed as the last of `remaining_accounts` enables the verification
+/// of the signatures by the secp256k1 precompile instructions of the transaction.
+pub fn write_price<'info>(
+    ctx: Context<'_, '_, 'info, 'info, WritePrice<'info>>,
+    feed_id: FeedIdBs,
+    payload: Vec<u8>,
+) -> Result<()> {
+    let config_account = &ctx.accounts.config_account;
+    config_account.verify()?;
+    config_account.verify_updater(&ctx.accounts.user.key())?;
+    require!(
+        !ctx.accounts.price_account.paused,
+        PriceAdapterError::FeedPaused
+    );
+
+    // the account has just been created by `init_if_needed`
+    if ctx.accounts.price_account.write_timestamp.is_none() {
+        require!(
+            config_account.is_feed_allowed(&feed_id),
+            PriceAdapterError::FeedNotAllowed
+        );
+    }
+
     let feed_id = feed_id.into();
     let block_timestamp = current_time_as_millis()?;
 
-    let mut config: SolanaRedStoneConfig =
-        SOLANA_CONFIG.redstone_config((), vec![feed_id], block_timestamp)?;
+    let (remaining_accounts, instructions_sysvar) =
+        split_instructions_sysvar(ctx.remaining_accounts);
+
+    let processed_payload = match instructions_sysvar {
+        Some(instructions_sysvar) => {
+            let crypto = PrecompileCrypto::load(instructions_sysvar)?;
+            let mut config =
+                config_account.redstone_config(crypto, vec![feed_id], block_timestamp)?;
 
-    let processed_payload = process_payload(&mut config, payload)?;
+            process_payload(&mut config, payload)?
+        }
+        None => {
+            let mut config: SolanaRedStoneConfig =
+                config_account.redstone_config((), vec![feed_id], block_timestamp)?;
+
+            process_payload(&mut config, payload)?
+        }
+    };
 
     if processed_payload.values.is_empty() {
         return Err(RedStoneError::ArrayIsEmpty.into());
@@ -49,21 +94,33 @@ pub fn write_price(ctx: Context<WritePrice>, feed_id: FeedIdBs, payload: Vec<u8>
     let FeedValue { value, .. } = processed_payload.values[0];
     let price_account = &mut ctx.accounts.price_account;
 
-    UpdateTimestampVerifier::verifier(&ctx.accounts.user.key(), &SOLANA_CONFIG.trusted_updaters)
-        .verify_timestamp(
-            block_timestamp,
-            price_account.write_timestamp.map(Into::into),
-            SOLANA_CONFIG.min_interval_between_updates_ms.into(),
-            Some(price_account.timestamp.into()),
+    require!(
+        is_update_required(
+            price_account,
+            config_account,
+            &ctx.accounts.user.key(),
+            &value.0,
             processed_payload.timestamp,
-        )?;
+        ),
+        PriceAdapterError::UpdateNotRequired
+    );
+
+    update_price_data(
+        price_account,
+        config_account,
+        &ctx.accounts.user.key(),
+        feed_id.into(),
+        value.0,
+        processed_payload.timestamp,
+        block_timestamp,
+    )?;
+
+    for optional_account in remaining_accounts.iter().take(2) {
+        record_optional_account(optional_account, price_account)?;
+    }
 
-    price_account.value = value.0;
-    price_account.timestamp = processed_payload.timestamp.as_millis();
-    price_account.feed_id = feed_id.into();
-    price_account.write_timestamp = Some(block_timestamp.as_millis());
-    price_account.write_slot_number = Clock::get()?.slot;
-    price_account.decimals = REDSTONE_DECIMALS_EXP;
+    let event = PriceUpdated::new(price_account, ctx.accounts.user.key());
+    emit_event!(ctx, event);
 
     debug_msg(|| {
         format!(
@@ -76,3 +133,63 @@ pub fn write_price(ctx: Context<WritePrice>, feed_id: FeedIdBs, payload: Vec<u8>
 
     Ok(())
 }
+
+/// Appends the just written `price_data` to the `PriceHistory` or refreshes the `ScaledPriceData`
+/// mirror, passed as an optional account of the write instructions and told apart by the discriminator.
+pub(crate) fn record_optional_account<'info>(
+    account: &'info AccountInfo<'info>,
+    price_data: &PriceData,
+) -> Result<()> {
+    let is_scaled_price = account
+        .try_borrow_data()?
+        .starts_with(ScaledPriceData::DISCRIMINATOR);
+
+    if is_scaled_price {
+        record_scaled_price(account, price_data)
+    } else {
+        record_price_history(account, price_data)
+    }
+}
+
+/// Trusted updaters can write the price regardless of the heartbeat and the deviation threshold of the feed.
+pub(crate) fn is_update_required(
+    price_data: &PriceData,
+    config: &ConfigAccount,
+    updater: &Pubkey,
+    value: &ValueBs,
+    package_timestamp: TimestampMillis,
+) -> bool {
+    config.trusted_updaters.contains(updater)
+        || price_data.is_update_required(value, package_timestamp.as_millis())
+}
+
+pub(crate) fn update_price_data(
+    price_data: &mut PriceData,
+    config: &ConfigAccount,
+    updater: &Pubkey,
+    feed_id: FeedIdBs,
+    value: ValueBs,
+    package_timestamp: TimestampMillis,
+    block_timestamp: TimestampMillis,
+) -> Result<()> {
+    UpdateTimestampVerifier::verifier(updater, config.trusted_updaters.as_slice())
+        .verify_timestamp(
+            block_timestamp,
+            price_data.write_timestamp.map(Into::into),
+            price_data
+                .metadata
+                .min_interval_between_updates_ms(config)
+                .into(),
+            Some(price_data.timestamp.into()),
+            package_timestamp,
+        )?;
+
+    price_data.value = value;
+    price_data.timestamp = package_timestamp.as_millis();
+    price_data.feed_id = feed_id;
+    price_data.write_timestamp = Some(block_timestamp.as_millis());
+    price_data.write_slot_number = Clock::get()?.slot;
+    price_data.decimals = price_data.metadata.decimals();
+
+    Ok(())
+}
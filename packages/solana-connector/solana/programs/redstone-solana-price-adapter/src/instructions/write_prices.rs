@@ -0,0 +1,286 @@
+use crate::{
+    crypto::{split_instructions_sysvar, PrecompileCrypto},
+    error::PriceAdapterError,
+    events::{emit_event, PriceUpdateRejected, PriceUpdated},
+    instructions::write_price::{is_update_required, record_optional_account, update_price_data},
+    state::{ConfigAccount, PriceData, PRICE_DATA_SPACE},
+    util::{current_time_as_millis, debug_msg, make_config_seed, make_price_seed},
+    FeedIdBs,
+};
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Allocate, Assign, CreateAccount, Transfer};
+use redstone::{
+    core::processor::process_payload, network::as_str::AsHexStr, solana::SolanaRedStoneConfig,
+    ConfigFactory, FeedValue,
+};
+
+/// The `PriceData` accounts of the written feeds are passed via
+/// `remaining_accounts`, in the order of `feed_ids`, optionally followed
+/// by their `PriceHistory` accounts and/or their `ScaledPriceData` mirrors, each group in the order of `feed_ids`,
+/// and the instructions sysvar enabling the verification of the signatures by the secp256k1 precompile.
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct WritePrices<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(seeds = [&make_config_seed()], bump)]
+    pub config_account: Account<'info, ConfigAccount>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn write_prices<'info>(
+    ctx: Context<'_, '_, 'info, 'info, WritePrices<'info>>,
+    feed_ids: Vec<FeedIdBs>,
+    payload: Vec<u8>,
+) -> Result<()> {
+    let writer = FeedWriter {
+        program_id: ctx.program_id,
+        user: &ctx.accounts.user,
+        config_account: &ctx.accounts.config_account,
+        system_program: &ctx.accounts.system_program,
+    };
+
+    for feed_write in writer.write(ctx.remaining_accounts, &feed_ids, payload)? {
+        match feed_write {
+            FeedWrite::Updated(price_data) => {
+                let event = PriceUpdated::new(&price_data, ctx.accounts.user.key());
+                emit_event!(ctx, event);
+            }
+            FeedWrite::Rejected(event) => emit_event!(ctx, event),
+        }
+    }
+
+    Ok(())
+}
+
+/// The outcome of writing a single feed by [`FeedWriter::write`].
+pub(crate) enum FeedWrite {
+    Updated(PriceData),
+    Rejected(PriceUpdateRejected),
+}
+
+/// The accounts shared by the instructions writing multiple feeds at once.
+pub(crate) struct FeedWriter<'a, 'info> {
+    pub program_id: &'a Pubkey,
+    pub user: &'a Signer<'info>,
+    pub config_account: &'a Account<'info, ConfigAccount>,
+    pub system_program: &'a Program<'info, System>,
+}
+
+impl<'info> FeedWriter<'_, 'info> {
+    /// Writes the `feed_ids` with the `remaining_accounts` as described for [`WritePrices`],
+    /// returning the updated `PriceData` and the rejections of the skipped feeds.
+    pub fn write(
+        &self,
+        remaining_accounts: &'info [AccountInfo<'info>],
+        feed_ids: &[FeedIdBs],
+        payload: Vec<u8>,
+    ) -> Result<Vec<FeedWrite>> {
+        let (remaining_accounts, instructions_sysvar) =
+            split_instructions_sysvar(remaining_accounts);
+        let account_count = remaining_accounts.len();
+        require!(
+            (1..=3).any(|group_count| account_count == group_count * feed_ids.len()),
+            PriceAdapterError::PriceAccountCountMismatch
+        );
+        let (price_accounts, optional_accounts) = remaining_accounts.split_at(feed_ids.len());
+        require!(
+            feed_ids
+                .iter()
+                .enumerate()
+                .all(|(index, feed_id)| !feed_ids[..index].contains(feed_id)),
+            PriceAdapterError::DuplicateFeedId
+        );
+
+        let block_timestamp = current_time_as_millis()?;
+        let config_account = self.config_account;
+        config_account.verify()?;
+        config_account.verify_updater(&self.user.key())?;
+
+        let redstone_feed_ids = feed_ids.iter().map(|&feed_id| feed_id.into()).collect();
+        let processed_payload = match instructions_sysvar {
+            Some(instructions_sysvar) => {
+                let crypto = PrecompileCrypto::load(instructions_sysvar)?;
+                let mut config =
+                    config_account.redstone_config(crypto, redstone_feed_ids, block_timestamp)?;
+
+                process_payload(&mut config, payload)?
+            }
+            None => {
+                let mut config: SolanaRedStoneConfig =
+                    config_account.redstone_config((), redstone_feed_ids, block_timestamp)?;
+
+                process_payload(&mut config, payload)?
+            }
+        };
+
+        let mut feed_writes = vec![];
+        for (index, (feed_id, price_account)) in feed_ids.iter().zip(price_accounts).enumerate() {
+            let FeedValue { value, .. } = processed_payload
+                .values
+                .iter()
+                .find(|FeedValue { feed, .. }| FeedIdBs::from(*feed) == *feed_id)
+                .ok_or(PriceAdapterError::MissingFeedValue)?;
+
+            let mut price_data = self.load_or_init_price_account(feed_id, price_account)?;
+
+            // the paused feeds and the feeds which don't need an update are skipped,
+            // so they don't fail the whole batch
+            let rejection = if price_data.paused {
+                Some(PriceAdapterError::FeedPaused)
+            } else if !is_update_required(
+                &price_data,
+                config_account,
+                &self.user.key(),
+                &value.0,
+                processed_payload.timestamp,
+            ) {
+                Some(PriceAdapterError::UpdateNotRequired)
+            } else {
+                None
+            };
+
+            if let Some(error) = rejection {
+                feed_writes.push(FeedWrite::Rejected(PriceUpdateRejected::new(
+                    *feed_id,
+                    processed_payload.timestamp.as_millis(),
+                    self.user.key(),
+                    error.into(),
+                )));
+                continue;
+            }
+
+            update_price_data(
+                &mut price_data,
+                config_account,
+                &self.user.key(),
+                *feed_id,
+                value.0,
+                processed_payload.timestamp,
+                block_timestamp,
+            )?;
+
+            price_data.try_serialize(&mut &mut price_account.try_borrow_mut_data()?[..])?;
+
+            for optional_account in optional_accounts.iter().skip(index).step_by(feed_ids.len()) {
+                record_optional_account(optional_account, &price_data)?;
+            }
+
+            debug_msg(|| {
+                format!(
+                    "{} {}: {:?}",
+                    price_data.timestamp,
+                    feed_id.as_hex_str(),
+                    value.0,
+                )
+            });
+
+            feed_writes.push(FeedWrite::Updated(price_data));
+        }
+
+        Ok(feed_writes)
+    }
+
+    fn load_or_init_price_account(
+        &self,
+        feed_id: &FeedIdBs,
+        price_account: &AccountInfo<'info>,
+    ) -> Result<PriceData> {
+        let (address, bump) =
+            Pubkey::find_program_address(&[&make_price_seed(), feed_id], self.program_id);
+
+        require_keys_eq!(
+            price_account.key(),
+            address,
+            PriceAdapterError::InvalidPriceAccount
+        );
+        require!(
+            price_account.is_writable,
+            PriceAdapterError::PriceAccountNotWritable
+        );
+
+        if price_account.owner == self.program_id {
+            return PriceData::try_deserialize(&mut &price_account.try_borrow_data()?[..]);
+        }
+        require_keys_eq!(
+            *price_account.owner,
+            system_program::ID,
+            ErrorCode::AccountOwnedByWrongProgram
+        );
+
+        require!(
+            self.config_account.is_feed_allowed(feed_id),
+            PriceAdapterError::FeedNotAllowed
+        );
+
+        let signer_seeds: &[&[&[u8]]] = &[&[&make_price_seed(), feed_id, &[bump]]];
+        self.create_price_account(price_account, signer_seeds)?;
+
+        Ok(PriceData::default())
+    }
+
+    /// Creates the account the same way as the `init` constraint does, so accounts
+    /// which already hold some lamports can be initialized as well.
+    fn create_price_account(
+        &self,
+        price_account: &AccountInfo<'info>,
+        signer_seeds: &[&[&[u8]]],
+    ) -> Result<()> {
+        let system_program = self.system_program.to_account_info();
+        let user = self.user.to_account_info();
+        let required_lamports = Rent::get()?.minimum_balance(PRICE_DATA_SPACE);
+        let current_lamports = price_account.lamports();
+
+        if current_lamports == 0 {
+            return system_program::create_account(
+                CpiContext::new_with_signer(
+                    system_program,
+                    CreateAccount {
+                        from: user,
+                        to: price_account.clone(),
+                    },
+                    signer_seeds,
+                ),
+                required_lamports,
+                PRICE_DATA_SPACE as u64,
+                self.program_id,
+            );
+        }
+
+        let missing_lamports = required_lamports.saturating_sub(current_lamports);
+        if missing_lamports > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    system_program.clone(),
+                    Transfer {
+                        from: user,
+                        to: price_account.clone(),
+                    },
+                ),
+                missing_lamports,
+            )?;
+        }
+
+        system_program::allocate(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                Allocate {
+                    account_to_allocate: price_account.clone(),
+                },
+                signer_seeds,
+            ),
+            PRICE_DATA_SPACE as u64,
+        )?;
+
+        system_program::assign(
+            CpiContext::new_with_signer(
+                system_program,
+                Assign {
+                    account_to_assign: price_account.clone(),
+                },
+                signer_seeds,
+            ),
+            self.program_id,
+        )
+    }
+}
@@ -1,22 +1,98 @@
 pub mod config;
+pub mod crypto;
+pub mod error;
+pub mod events;
 pub mod instructions;
 pub mod state;
 pub mod util;
 
 use crate::instructions::*;
 use anchor_lang::prelude::*;
-use state::{FeedIdBs, ValueBs};
+use state::{
+    ConfigParams, DerivedPrice, DerivedPriceFormula, FeedIdBs, FeedMetadata, HistoricalPrice,
+    ScaledPrice, ValueBs,
+};
 
 declare_id!("rds8J7VKqLQgzDr7vS59dkQga3B1BotgFy8F7LSLC74");
 
 #[program]
 pub mod redstone_solana_price_adapter {
     use super::*;
-    use crate::config::SOLANA_CONFIG;
     use util::debug_msg;
 
-    pub fn write_price(
-        ctx: Context<WritePrice>,
+    pub fn initialize_config(ctx: Context<InitializeConfig>) -> Result<()> {
+        instructions::initialize_config(ctx)
+    }
+
+    pub fn update_config(ctx: Context<UpdateConfig>, params: ConfigParams) -> Result<()> {
+        instructions::update_config(ctx, params)
+    }
+
+    pub fn set_feed_allowed(
+        ctx: Context<UpdateConfig>,
+        feed_id: FeedIdBs,
+        allowed: bool,
+    ) -> Result<()> {
+        instructions::set_feed_allowed(ctx, feed_id, allowed)
+    }
+
+    pub fn close_price_account(ctx: Context<ClosePriceAccount>, _feed_id: FeedIdBs) -> Result<()> {
+        instructions::close_price_account(ctx)
+    }
+
+    pub fn set_feed_metadata(
+        ctx: Context<SetFeedMetadata>,
+        feed_id: FeedIdBs,
+        metadata: FeedMetadata,
+    ) -> Result<()> {
+        instructions::set_feed_metadata(ctx, feed_id, metadata)
+    }
+
+    pub fn realloc_price_account(
+        ctx: Context<ReallocPriceAccount>,
+        _feed_id: FeedIdBs,
+    ) -> Result<()> {
+        instructions::realloc_price_account(ctx)
+    }
+
+    pub fn set_pauser(ctx: Context<UpdateConfig>, pauser: Pubkey) -> Result<()> {
+        instructions::set_pauser(ctx, pauser)
+    }
+
+    pub fn set_updater_allowed(
+        ctx: Context<UpdateConfig>,
+        updater: Pubkey,
+        allowed: bool,
+    ) -> Result<()> {
+        instructions::set_updater_allowed(ctx, updater, allowed)
+    }
+
+    pub fn set_updater_allow_list_enabled(ctx: Context<UpdateConfig>, enabled: bool) -> Result<()> {
+        instructions::set_updater_allow_list_enabled(ctx, enabled)
+    }
+
+    pub fn set_paused(ctx: Context<Pause>, paused: bool) -> Result<()> {
+        instructions::set_paused(ctx, paused)
+    }
+
+    pub fn set_feed_paused(
+        ctx: Context<PauseFeed>,
+        _feed_id: FeedIdBs,
+        paused: bool,
+    ) -> Result<()> {
+        instructions::set_feed_paused(ctx, paused)
+    }
+
+    pub fn transfer_admin(ctx: Context<UpdateConfig>, new_admin: Pubkey) -> Result<()> {
+        instructions::transfer_admin(ctx, new_admin)
+    }
+
+    pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+        instructions::accept_admin(ctx)
+    }
+
+    pub fn write_price<'info>(
+        ctx: Context<'_, '_, 'info, 'info, WritePrice<'info>>,
         feed_id: FeedIdBs,
         payload: Vec<u8>,
     ) -> Result<()> {
@@ -30,6 +106,57 @@ pub mod redstone_solana_price_adapter {
         instructions::write_price(ctx, feed_id, payload)
     }
 
+    pub fn write_prices<'info>(
+        ctx: Context<'_, '_, 'info, 'info, WritePrices<'info>>,
+        feed_ids: Vec<FeedIdBs>,
+        payload: Vec<u8>,
+    ) -> Result<()> {
+        debug_msg(|| {
+            format!(
+                "Processing redstone payload of size {} for {} feeds",
+                payload.len(),
+                feed_ids.len(),
+            )
+        });
+        instructions::write_prices(ctx, feed_ids, payload)
+    }
+
+    pub fn init_payload_buffer(
+        ctx: Context<InitPayloadBuffer>,
+        _buffer_id: u64,
+        capacity: u32,
+    ) -> Result<()> {
+        instructions::init_payload_buffer(ctx, capacity)
+    }
+
+    pub fn append_payload_chunk(
+        ctx: Context<AppendPayloadChunk>,
+        _buffer_id: u64,
+        offset: u32,
+        chunk: Vec<u8>,
+    ) -> Result<()> {
+        instructions::append_payload_chunk(ctx, offset, chunk)
+    }
+
+    pub fn close_payload_buffer(ctx: Context<ClosePayloadBuffer>, _buffer_id: u64) -> Result<()> {
+        instructions::close_payload_buffer(ctx)
+    }
+
+    pub fn write_prices_from_buffer<'info>(
+        ctx: Context<'_, '_, 'info, 'info, WritePricesFromBuffer<'info>>,
+        _buffer_id: u64,
+        feed_ids: Vec<FeedIdBs>,
+        payload_hash: [u8; 32],
+    ) -> Result<()> {
+        debug_msg(|| {
+            format!(
+                "Processing buffered redstone payload for {} feeds",
+                feed_ids.len(),
+            )
+        });
+        instructions::write_prices_from_buffer(ctx, feed_ids, payload_hash)
+    }
+
     pub fn price_and_timestamp(
         ctx: Context<ReadPrice>,
         _feed_id: FeedIdBs,
@@ -45,11 +172,50 @@ pub mod redstone_solana_price_adapter {
         Ok(instructions::timestamp(ctx))
     }
 
-    pub fn unique_signers_count(_ctx: Context<Dummy>) -> Result<u8> {
-        Ok(SOLANA_CONFIG.signer_count_threshold)
+    pub fn init_price_history(ctx: Context<InitPriceHistory>, feed_id: FeedIdBs) -> Result<()> {
+        instructions::init_price_history(ctx, feed_id)
+    }
+
+    pub fn latest_price_history(
+        ctx: Context<ReadPriceHistory>,
+        _feed_id: FeedIdBs,
+        count: u8,
+    ) -> Result<Vec<HistoricalPrice>> {
+        instructions::latest_price_history(ctx, count)
+    }
+
+    pub fn price_twap(
+        ctx: Context<ReadPriceHistory>,
+        _feed_id: FeedIdBs,
+        window_ms: u64,
+    ) -> Result<ValueBs> {
+        instructions::price_twap(ctx, window_ms)
     }
-}
 
-/// Use in view-like functions that does not require external parameters
-#[derive(Accounts)]
-pub struct Dummy {}
+    pub fn price_checked(
+        ctx: Context<ReadPrice>,
+        _feed_id: FeedIdBs,
+        max_age_ms: u64,
+    ) -> Result<ValueBs> {
+        instructions::price_checked(ctx, max_age_ms)
+    }
+
+    pub fn read_price_scaled(ctx: Context<ReadPrice>, _feed_id: FeedIdBs) -> Result<ScaledPrice> {
+        instructions::read_price_scaled(ctx)
+    }
+
+    pub fn update_scaled_price(ctx: Context<UpdateScaledPrice>, _feed_id: FeedIdBs) -> Result<()> {
+        instructions::update_scaled_price(ctx)
+    }
+
+    pub fn read_derived_price(
+        ctx: Context<ReadDerivedPrice>,
+        formula: DerivedPriceFormula,
+    ) -> Result<DerivedPrice> {
+        instructions::read_derived_price(ctx, formula)
+    }
+
+    pub fn unique_signers_count(ctx: Context<ReadConfig>) -> Result<u8> {
+        Ok(ctx.accounts.config_account.signer_count_threshold)
+    }
+}
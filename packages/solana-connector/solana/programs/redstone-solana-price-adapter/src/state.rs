@@ -1,10 +1,41 @@
 use anchor_lang::prelude::*;
 
+use crate::error::PriceAdapterError;
+use primitive_types::U256;
+
 pub type FeedIdBs = [u8; 32];
 pub type ValueBs = [u8; 32];
+pub type SignerAddressBs = [u8; 20];
 
-const RESERVED_BYTE_SIZE: usize = 64;
+const RESERVED_BYTE_SIZE: usize = 64 - FEED_METADATA_SIZE - 1;
+const FEED_METADATA_SIZE: usize = 8 + 2 + 8 + 1;
+const BPS_DENOMINATOR: u128 = 10_000;
 pub const REDSTONE_DECIMALS_EXP: u8 = 8;
+pub const PRICE_DATA_SPACE: usize = 8 + std::mem::size_of::<PriceData>();
+pub const SCALED_PRICE_DATA_SPACE: usize = 8 + std::mem::size_of::<ScaledPriceData>();
+pub const PRICE_HISTORY_CAPACITY: usize = 128;
+pub const PRICE_HISTORY_SPACE: usize = 8 + std::mem::size_of::<PriceHistory>();
+
+/// Stays below the 10 KiB limit of the account data allocated by a CPI.
+pub const MAX_PAYLOAD_BUFFER_CAPACITY: usize = 10_000;
+
+pub const MAX_SIGNER_COUNT: usize = 32;
+pub const MAX_TRUSTED_UPDATER_COUNT: usize = 8;
+pub const MAX_ALLOWED_FEED_COUNT: usize = 128;
+pub const MAX_UPDATER_COUNT: usize = 16;
+pub const MAX_DERIVED_PRICE_COMPONENTS: usize = 8;
+pub const CONFIG_ACCOUNT_SPACE: usize = 8
+    + 32
+    + (1 + 32)
+    + 1
+    + (4 + MAX_SIGNER_COUNT * 20)
+    + (4 + MAX_TRUSTED_UPDATER_COUNT * 32)
+    + 3 * 8
+    + (4 + MAX_ALLOWED_FEED_COUNT * 32)
+    + 32
+    + 1
+    + 1
+    + (4 + MAX_UPDATER_COUNT * 32);
 
 #[account]
 pub struct PriceData {
@@ -14,9 +45,42 @@ pub struct PriceData {
     pub write_timestamp: Option<u64>,
     pub write_slot_number: u64,
     pub decimals: u8,
+    pub metadata: FeedMetadata,
+    /// Set by the pauser to reject the writes of the feed.
+    pub paused: bool,
     pub _reserved: [u8; RESERVED_BYTE_SIZE],
 }
 
+/// Per-feed configuration set by the admin, stored in the formerly reserved bytes of [`PriceData`].
+/// The zeroed metadata of the existing accounts keeps the former behavior.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FeedMetadata {
+    /// Time between the package timestamps after which an update is always accepted, 0 if not checked.
+    pub heartbeat_ms: u64,
+    /// Price change in basis points for which an update is accepted before the heartbeat, 0 if not checked.
+    pub deviation_threshold_bps: u16,
+    /// Overrides the `min_interval_between_updates_ms` of the config when not 0.
+    pub min_interval_between_updates_ms: u64,
+    /// Decimals of the feed values when they differ from [`REDSTONE_DECIMALS_EXP`], 0 for the default.
+    pub decimals: u8,
+}
+
+impl FeedMetadata {
+    pub fn decimals(&self) -> u8 {
+        match self.decimals {
+            0 => REDSTONE_DECIMALS_EXP,
+            decimals => decimals,
+        }
+    }
+
+    pub fn min_interval_between_updates_ms(&self, config: &ConfigAccount) -> u64 {
+        match self.min_interval_between_updates_ms {
+            0 => config.min_interval_between_updates_ms,
+            interval => interval,
+        }
+    }
+}
+
 impl Default for PriceData {
     fn default() -> Self {
         Self {
@@ -26,7 +90,562 @@ impl Default for PriceData {
             write_timestamp: Default::default(),
             write_slot_number: Default::default(),
             decimals: REDSTONE_DECIMALS_EXP,
+            metadata: Default::default(),
+            paused: false,
             _reserved: [Default::default(); RESERVED_BYTE_SIZE],
         }
     }
 }
+
+fn value_to_u128(value: &ValueBs) -> Result<u128> {
+    let (high, low) = value.split_at(value.len() - 16);
+    require!(
+        high.iter().all(|&byte| byte == 0),
+        PriceAdapterError::PriceOverflow
+    );
+
+    Ok(u128::from_be_bytes(low.try_into().unwrap()))
+}
+
+fn u128_to_value(value: u128) -> ValueBs {
+    let mut bytes = ValueBs::default();
+    bytes[16..].copy_from_slice(&value.to_be_bytes());
+
+    bytes
+}
+
+impl PriceData {
+    /// Converts the stored value to a Pyth-style `i64` price with a negative exponent.
+    pub fn scaled_price(&self) -> Result<ScaledPrice> {
+        let price = i64::try_from(value_to_u128(&self.value)?)
+            .map_err(|_| PriceAdapterError::PriceOverflow)?;
+
+        Ok(ScaledPrice {
+            price,
+            conf: 0,
+            expo: -i32::from(self.decimals),
+            publish_time: (self.timestamp / 1_000) as i64,
+        })
+    }
+
+    /// Checks whether the `value` from a package of `package_timestamp` is past the heartbeat
+    /// or beyond the deviation threshold of the feed.
+    /// The first write and the feeds without any of them set always require an update.
+    pub fn is_update_required(&self, value: &ValueBs, package_timestamp: u64) -> bool {
+        let FeedMetadata {
+            heartbeat_ms,
+            deviation_threshold_bps,
+            ..
+        } = self.metadata;
+
+        if self.write_timestamp.is_none() || (heartbeat_ms == 0 && deviation_threshold_bps == 0) {
+            return true;
+        }
+
+        if heartbeat_ms != 0 && package_timestamp.saturating_sub(self.timestamp) >= heartbeat_ms {
+            return true;
+        }
+
+        if deviation_threshold_bps == 0 {
+            return false;
+        }
+
+        let (Ok(current), Ok(new)) = (value_to_u128(&self.value), value_to_u128(value)) else {
+            return true;
+        };
+
+        current == 0
+            || current.abs_diff(new).saturating_mul(BPS_DENOMINATOR)
+                >= current.saturating_mul(deviation_threshold_bps.into())
+    }
+}
+
+/// The price in the layout used by Pyth and Switchboard integrators:
+/// `price * 10^expo`, published at `publish_time` given in seconds.
+/// RedStone doesn't provide a confidence interval, so `conf` is always 0.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ScaledPrice {
+    pub price: i64,
+    pub conf: u64,
+    pub expo: i32,
+    pub publish_time: i64,
+}
+
+/// Mirror of [`PriceData`] kept in the [`ScaledPrice`] layout,
+/// for programs reading the price accounts directly.
+#[account]
+pub struct ScaledPriceData {
+    pub feed_id: FeedIdBs,
+    pub price: i64,
+    pub conf: u64,
+    pub expo: i32,
+    pub publish_time: i64,
+    pub posted_slot: u64,
+}
+
+impl ScaledPriceData {
+    /// Copies the written `price_data`, so the mirror is never left zeroed.
+    pub fn update(&mut self, price_data: &PriceData) -> Result<()> {
+        require!(
+            price_data.write_timestamp.is_some() && price_data.timestamp > 0,
+            PriceAdapterError::PriceNotInitialized
+        );
+
+        let ScaledPrice {
+            price,
+            conf,
+            expo,
+            publish_time,
+        } = price_data.scaled_price()?;
+
+        self.feed_id = price_data.feed_id;
+        self.price = price;
+        self.conf = conf;
+        self.expo = expo;
+        self.publish_time = publish_time;
+        self.posted_slot = price_data.write_slot_number;
+
+        Ok(())
+    }
+}
+
+#[zero_copy]
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct PriceHistoryEntry {
+    pub value: ValueBs,
+    pub timestamp: u64,
+    pub write_slot: u64,
+}
+
+/// [`PriceHistoryEntry`] as returned by the read instructions.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct HistoricalPrice {
+    pub value: ValueBs,
+    pub timestamp: u64,
+    pub write_slot: u64,
+}
+
+impl From<&PriceHistoryEntry> for HistoricalPrice {
+    fn from(entry: &PriceHistoryEntry) -> Self {
+        Self {
+            value: entry.value,
+            timestamp: entry.timestamp,
+            write_slot: entry.write_slot,
+        }
+    }
+}
+
+/// Ring buffer of the last [`PRICE_HISTORY_CAPACITY`] values written for the feed.
+#[account(zero_copy)]
+pub struct PriceHistory {
+    pub feed_id: FeedIdBs,
+    /// Index the next entry is written at.
+    pub head: u64,
+    pub len: u64,
+    pub entries: [PriceHistoryEntry; PRICE_HISTORY_CAPACITY],
+}
+
+impl PriceHistory {
+    pub fn push(&mut self, entry: PriceHistoryEntry) {
+        self.entries[self.head as usize] = entry;
+        self.head = (self.head + 1) % PRICE_HISTORY_CAPACITY as u64;
+        self.len = (self.len + 1).min(PRICE_HISTORY_CAPACITY as u64);
+    }
+
+    /// Iterates the entries from the newest to the oldest one.
+    pub fn latest(&self) -> impl Iterator<Item = &PriceHistoryEntry> {
+        (1..=self.len as usize).map(move |i| {
+            &self.entries
+                [(self.head as usize + PRICE_HISTORY_CAPACITY - i) % PRICE_HISTORY_CAPACITY]
+        })
+    }
+
+    /// Time-weighted average of the values in the `[now - window_ms, now]` range,
+    /// each value weighted by the time until the next one (or `now`) was published.
+    /// If the history doesn't reach the beginning of the window, only the covered part is averaged.
+    pub fn twap(&self, now: u64, window_ms: u64) -> Result<ValueBs> {
+        let window_start = now.saturating_sub(window_ms);
+        let mut period_end = now;
+        let mut weighted_sum = 0u128;
+        let mut total_duration = 0u64;
+        let mut latest = None;
+
+        for entry in self.latest() {
+            latest.get_or_insert(entry.value);
+
+            let duration = period_end.saturating_sub(entry.timestamp.max(window_start));
+            weighted_sum = value_to_u128(&entry.value)?
+                .checked_mul(duration.into())
+                .and_then(|weighted| weighted_sum.checked_add(weighted))
+                .ok_or(PriceAdapterError::PriceOverflow)?;
+            total_duration += duration;
+
+            if entry.timestamp <= window_start {
+                break;
+            }
+            period_end = period_end.min(entry.timestamp);
+        }
+
+        let latest = latest.ok_or(PriceAdapterError::PriceNotInitialized)?;
+        if total_duration == 0 {
+            return Ok(latest);
+        }
+
+        Ok(u128_to_value(weighted_sum / u128::from(total_duration)))
+    }
+}
+
+#[account]
+pub struct ConfigAccount {
+    pub admin: Pubkey,
+    pub pending_admin: Option<Pubkey>,
+    pub signer_count_threshold: u8,
+    pub signers: Vec<SignerAddressBs>,
+    pub trusted_updaters: Vec<Pubkey>,
+    pub max_timestamp_delay_ms: u64,
+    pub max_timestamp_ahead_ms: u64,
+    pub min_interval_between_updates_ms: u64,
+    /// Feeds whose `PriceData` accounts can be created by the write instructions.
+    pub allowed_feed_ids: Vec<FeedIdBs>,
+    /// Can pause and unpause the writes, as well as the admin.
+    pub pauser: Pubkey,
+    pub paused: bool,
+    /// Restricts the writes to the `updaters` and the `trusted_updaters`.
+    pub updater_allow_list_enabled: bool,
+    pub updaters: Vec<Pubkey>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ConfigParams {
+    pub signer_count_threshold: u8,
+    pub signers: Vec<SignerAddressBs>,
+    pub trusted_updaters: Vec<Pubkey>,
+    pub max_timestamp_delay_ms: u64,
+    pub max_timestamp_ahead_ms: u64,
+    pub min_interval_between_updates_ms: u64,
+}
+
+/// Payload too large for a single transaction, filled in chunks by `append_payload_chunk`
+/// and consumed by `write_prices_from_buffer`.
+#[account]
+pub struct PayloadBuffer {
+    pub data: Vec<u8>,
+}
+
+pub const fn payload_buffer_space(capacity: usize) -> usize {
+    8 + 4 + capacity
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DerivedPriceOperation {
+    Multiply,
+    Divide,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DerivedPriceComponent {
+    pub feed_id: FeedIdBs,
+    pub operation: DerivedPriceOperation,
+}
+
+/// Price computed on read from the values of the `components`, applied one after another
+/// starting from 1, e.g. `stSOL/SOL * SOL/USD`, with `decimals` decimals of the result.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct DerivedPriceFormula {
+    pub components: Vec<DerivedPriceComponent>,
+    pub decimals: u8,
+}
+
+/// Result of the [`DerivedPriceFormula`], with the package timestamp of its oldest component.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DerivedPrice {
+    pub value: ValueBs,
+    pub decimals: u8,
+    pub timestamp: u64,
+}
+
+fn pow10(exponent: u8) -> Result<U256> {
+    U256::from(10)
+        .checked_pow(exponent.into())
+        .ok_or_else(|| PriceAdapterError::PriceOverflow.into())
+}
+
+impl DerivedPriceFormula {
+    /// Computes the price from the `PriceData` of the components, given in the same order.
+    /// The 256-bit fixed point result is rounded down after each component.
+    pub fn compute(&self, prices: &[PriceData]) -> Result<DerivedPrice> {
+        require!(
+            !self.components.is_empty() && self.components.len() <= MAX_DERIVED_PRICE_COMPONENTS,
+            PriceAdapterError::InvalidDerivedPriceFormula
+        );
+        require_eq!(
+            self.components.len(),
+            prices.len(),
+            PriceAdapterError::PriceAccountCountMismatch
+        );
+
+        let mut value = pow10(self.decimals)?;
+        let mut timestamp = u64::MAX;
+        for (component, price_data) in self.components.iter().zip(prices) {
+            let component_value = U256::from_big_endian(&price_data.value);
+            let unit = pow10(price_data.decimals)?;
+
+            value = match component.operation {
+                DerivedPriceOperation::Multiply => value
+                    .checked_mul(component_value)
+                    .and_then(|value| value.checked_div(unit)),
+                DerivedPriceOperation::Divide => {
+                    require!(
+                        !component_value.is_zero(),
+                        PriceAdapterError::DerivedPriceDivisionByZero
+                    );
+                    value
+                        .checked_mul(unit)
+                        .and_then(|value| value.checked_div(component_value))
+                }
+            }
+            .ok_or(PriceAdapterError::PriceOverflow)?;
+            timestamp = timestamp.min(price_data.timestamp);
+        }
+
+        Ok(DerivedPrice {
+            value: value.to_big_endian(),
+            decimals: self.decimals,
+            timestamp,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn price_data(value: u128, timestamp: u64) -> PriceData {
+        let mut price_data = PriceData::default();
+        price_data.value[16..].copy_from_slice(&value.to_be_bytes());
+        price_data.timestamp = timestamp;
+
+        price_data
+    }
+
+    #[test]
+    fn scaled_price_uses_negative_exponent_and_seconds() {
+        let scaled = price_data(6_543_210_000_000, 1_744_000_123_456)
+            .scaled_price()
+            .unwrap();
+
+        assert_eq!(
+            scaled,
+            ScaledPrice {
+                price: 6_543_210_000_000,
+                conf: 0,
+                expo: -8,
+                publish_time: 1_744_000_123,
+            }
+        );
+    }
+
+    fn history(entries: &[(u128, u64)]) -> PriceHistory {
+        let mut history = PriceHistory {
+            feed_id: Default::default(),
+            head: 0,
+            len: 0,
+            entries: [Default::default(); PRICE_HISTORY_CAPACITY],
+        };
+        for &(value, timestamp) in entries {
+            history.push(PriceHistoryEntry {
+                value: u128_to_value(value),
+                timestamp,
+                write_slot: 0,
+            });
+        }
+
+        history
+    }
+
+    #[test]
+    fn history_wraps_around_capacity() {
+        let entries: Vec<_> = (1..=PRICE_HISTORY_CAPACITY as u64 + 2)
+            .map(|i| (i as u128, i * 1_000))
+            .collect();
+        let history = history(&entries);
+
+        assert_eq!(history.len, PRICE_HISTORY_CAPACITY as u64);
+        let timestamps: Vec<_> = history.latest().map(|entry| entry.timestamp).collect();
+        assert_eq!(timestamps.len(), PRICE_HISTORY_CAPACITY);
+        assert_eq!(timestamps[0], (PRICE_HISTORY_CAPACITY as u64 + 2) * 1_000);
+        assert_eq!(timestamps[PRICE_HISTORY_CAPACITY - 1], 3_000);
+    }
+
+    #[test]
+    fn twap_weights_values_by_duration() {
+        let history = history(&[(100, 0), (200, 10_000), (400, 20_000)]);
+
+        // 100 for 5s, 200 for 10s, 400 for 5s
+        assert_eq!(
+            history.twap(25_000, 20_000).unwrap(),
+            u128_to_value((100 * 5 + 200 * 10 + 400 * 5) / 20)
+        );
+        // the history doesn't cover the whole window
+        assert_eq!(
+            history.twap(25_000, 100_000).unwrap(),
+            u128_to_value((100 * 10 + 200 * 10 + 400 * 5) / 25)
+        );
+        assert_eq!(history.twap(25_000, 0).unwrap(), u128_to_value(400));
+    }
+
+    #[test]
+    fn twap_of_empty_history() {
+        assert!(history(&[]).twap(25_000, 20_000).is_err());
+    }
+
+    #[test]
+    fn scaled_price_overflow() {
+        assert!(price_data(i64::MAX as u128, 0).scaled_price().is_ok());
+        assert!(price_data(i64::MAX as u128 + 1, 0).scaled_price().is_err());
+        assert!(price_data(u64::MAX as u128 + 1, 0).scaled_price().is_err());
+    }
+
+    fn written_price_data(heartbeat_ms: u64, deviation_threshold_bps: u16) -> PriceData {
+        let mut price_data = price_data(10_000, 100_000);
+        price_data.write_timestamp = Some(100_500);
+        price_data.metadata = FeedMetadata {
+            heartbeat_ms,
+            deviation_threshold_bps,
+            ..Default::default()
+        };
+
+        price_data
+    }
+
+    #[test]
+    fn update_required_without_metadata() {
+        let price_data = written_price_data(0, 0);
+
+        assert!(price_data.is_update_required(&u128_to_value(10_000), 100_001));
+    }
+
+    #[test]
+    fn update_required_for_first_write() {
+        let mut price_data = written_price_data(60_000, 100);
+        price_data.write_timestamp = None;
+
+        assert!(price_data.is_update_required(&u128_to_value(10_000), 100_001));
+    }
+
+    #[test]
+    fn update_required_past_heartbeat() {
+        let price_data = written_price_data(60_000, 0);
+
+        assert!(!price_data.is_update_required(&u128_to_value(20_000), 159_999));
+        assert!(price_data.is_update_required(&u128_to_value(10_000), 160_000));
+    }
+
+    #[test]
+    fn update_required_beyond_deviation() {
+        let price_data = written_price_data(60_000, 100);
+
+        assert!(!price_data.is_update_required(&u128_to_value(10_099), 100_001));
+        assert!(!price_data.is_update_required(&u128_to_value(9_901), 100_001));
+        assert!(price_data.is_update_required(&u128_to_value(10_100), 100_001));
+        assert!(price_data.is_update_required(&u128_to_value(9_900), 100_001));
+    }
+
+    #[test]
+    fn feed_metadata_defaults() {
+        let metadata = FeedMetadata::default();
+
+        assert_eq!(metadata.decimals(), REDSTONE_DECIMALS_EXP);
+        assert_eq!(
+            FeedMetadata {
+                decimals: 18,
+                ..metadata
+            }
+            .decimals(),
+            18
+        );
+    }
+
+    fn component(name: &[u8], operation: DerivedPriceOperation) -> DerivedPriceComponent {
+        let mut feed_id = FeedIdBs::default();
+        feed_id[..name.len()].copy_from_slice(name);
+
+        DerivedPriceComponent { feed_id, operation }
+    }
+
+    fn decimal_price_data(value: u128, decimals: u8, timestamp: u64) -> PriceData {
+        PriceData {
+            decimals,
+            ..price_data(value, timestamp)
+        }
+    }
+
+    #[test]
+    fn derived_price_of_product_and_ratio() {
+        // stSOL/SOL = 1.15 with 18 decimals, SOL/USD = 150.5 with 8 decimals, USDC/USD = 0.999 with 6 decimals
+        let formula = DerivedPriceFormula {
+            components: vec![
+                component(b"stSOL/SOL", DerivedPriceOperation::Multiply),
+                component(b"SOL", DerivedPriceOperation::Multiply),
+                component(b"USDC", DerivedPriceOperation::Divide),
+            ],
+            decimals: 8,
+        };
+        let prices = [
+            decimal_price_data(1_150_000_000_000_000_000, 18, 3_000),
+            decimal_price_data(15_050_000_000, 8, 1_000),
+            decimal_price_data(999_000, 6, 2_000),
+        ];
+
+        let derived = formula.compute(&prices).unwrap();
+
+        // 1.15 * 150.5 / 0.999 = 173.24824824...
+        assert_eq!(derived.value, u128_to_value(17_325_325_325));
+        assert_eq!(derived.decimals, 8);
+        assert_eq!(derived.timestamp, 1_000);
+    }
+
+    #[test]
+    fn derived_price_beyond_u128() {
+        let formula = DerivedPriceFormula {
+            components: vec![
+                component(b"A", DerivedPriceOperation::Multiply),
+                component(b"B", DerivedPriceOperation::Divide),
+            ],
+            decimals: 18,
+        };
+        let prices = [
+            decimal_price_data(u128::MAX, 0, 0),
+            decimal_price_data(u128::MAX, 0, 0),
+        ];
+
+        let derived = formula.compute(&prices).unwrap();
+
+        assert_eq!(derived.value, u128_to_value(10u128.pow(18)));
+    }
+
+    #[test]
+    fn derived_price_division_by_zero() {
+        let formula = DerivedPriceFormula {
+            components: vec![component(b"A", DerivedPriceOperation::Divide)],
+            decimals: 8,
+        };
+
+        assert!(formula.compute(&[decimal_price_data(0, 8, 0)]).is_err());
+    }
+
+    #[test]
+    fn derived_price_invalid_formula() {
+        let empty = DerivedPriceFormula {
+            components: vec![],
+            decimals: 8,
+        };
+        assert!(empty.compute(&[]).is_err());
+
+        let formula = DerivedPriceFormula {
+            components: vec![component(b"A", DerivedPriceOperation::Multiply)],
+            decimals: 8,
+        };
+        assert!(formula.compute(&[]).is_err());
+    }
+}
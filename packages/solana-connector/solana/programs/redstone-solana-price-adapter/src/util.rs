@@ -8,6 +8,30 @@ pub fn make_price_seed() -> [u8; 32] {
     seed
 }
 
+pub fn make_scaled_price_seed() -> [u8; 32] {
+    let mut seed = [0u8; 32];
+    seed[0..6].copy_from_slice(b"scaled");
+    seed
+}
+
+pub fn make_price_history_seed() -> [u8; 32] {
+    let mut seed = [0u8; 32];
+    seed[0..7].copy_from_slice(b"history");
+    seed
+}
+
+pub fn make_payload_buffer_seed() -> [u8; 32] {
+    let mut seed = [0u8; 32];
+    seed[0..6].copy_from_slice(b"buffer");
+    seed
+}
+
+pub fn make_config_seed() -> [u8; 32] {
+    let mut seed = [0u8; 32];
+    seed[0..6].copy_from_slice(b"config");
+    seed
+}
+
 #[cfg(feature = "dev")]
 pub fn bytes_to_hex(bytes: &[u8]) -> String {
     use std::fmt::Write;
@@ -13,6 +13,214 @@ export type RedstoneSolanaPriceAdapter = {
     description: "RedStone Solana Price Adapter";
   };
   instructions: [
+    {
+      name: "acceptAdmin";
+      discriminator: [112, 42, 45, 90, 116, 181, 13, 170];
+      accounts: [
+        {
+          name: "pendingAdmin";
+          signer: true;
+        },
+        {
+          name: "configAccount";
+          writable: true;
+        },
+      ];
+      args: [];
+    },
+    {
+      name: "appendPayloadChunk";
+      discriminator: [42, 60, 82, 71, 132, 102, 210, 82];
+      accounts: [
+        {
+          name: "user";
+          signer: true;
+        },
+        {
+          name: "payloadBuffer";
+          writable: true;
+        },
+      ];
+      args: [
+        {
+          name: "bufferId";
+          type: "u64";
+        },
+        {
+          name: "offset";
+          type: "u32";
+        },
+        {
+          name: "chunk";
+          type: "bytes";
+        },
+      ];
+    },
+    {
+      name: "closePayloadBuffer";
+      discriminator: [156, 123, 189, 188, 245, 71, 121, 96];
+      accounts: [
+        {
+          name: "user";
+          writable: true;
+          signer: true;
+        },
+        {
+          name: "payloadBuffer";
+          writable: true;
+        },
+      ];
+      args: [
+        {
+          name: "bufferId";
+          type: "u64";
+        },
+      ];
+    },
+    {
+      name: "closePriceAccount";
+      discriminator: [159, 255, 82, 231, 123, 207, 238, 204];
+      accounts: [
+        {
+          name: "admin";
+          signer: true;
+          relations: ["configAccount"];
+        },
+        {
+          name: "configAccount";
+        },
+        {
+          name: "priceAccount";
+          writable: true;
+        },
+        {
+          name: "receiver";
+          writable: true;
+        },
+        {
+          name: "priceHistory";
+          writable: true;
+          optional: true;
+        },
+        {
+          name: "scaledPriceAccount";
+          writable: true;
+          optional: true;
+        },
+      ];
+      args: [
+        {
+          name: "feedId";
+          type: {
+            array: ["u8", 32];
+          };
+        },
+      ];
+    },
+    {
+      name: "initPayloadBuffer";
+      discriminator: [192, 172, 229, 110, 248, 202, 169, 173];
+      accounts: [
+        {
+          name: "user";
+          writable: true;
+          signer: true;
+        },
+        {
+          name: "payloadBuffer";
+          writable: true;
+        },
+        {
+          name: "systemProgram";
+          address: "11111111111111111111111111111111";
+        },
+      ];
+      args: [
+        {
+          name: "bufferId";
+          type: "u64";
+        },
+        {
+          name: "capacity";
+          type: "u32";
+        },
+      ];
+    },
+    {
+      name: "initPriceHistory";
+      discriminator: [73, 13, 136, 91, 237, 247, 1, 198];
+      accounts: [
+        {
+          name: "user";
+          writable: true;
+          signer: true;
+        },
+        {
+          name: "priceHistory";
+          writable: true;
+        },
+        {
+          name: "systemProgram";
+          address: "11111111111111111111111111111111";
+        },
+      ];
+      args: [
+        {
+          name: "feedId";
+          type: {
+            array: ["u8", 32];
+          };
+        },
+      ];
+    },
+    {
+      name: "initializeConfig";
+      discriminator: [208, 127, 21, 1, 194, 190, 196, 70];
+      accounts: [
+        {
+          name: "payer";
+          writable: true;
+          signer: true;
+        },
+        {
+          name: "configAccount";
+          writable: true;
+        },
+        {
+          name: "systemProgram";
+          address: "11111111111111111111111111111111";
+        },
+      ];
+      args: [];
+    },
+    {
+      name: "latestPriceHistory";
+      discriminator: [151, 112, 205, 103, 13, 202, 29, 110];
+      accounts: [
+        {
+          name: "priceHistory";
+        },
+      ];
+      args: [
+        {
+          name: "feedId";
+          type: {
+            array: ["u8", 32];
+          };
+        },
+        {
+          name: "count";
+          type: "u8";
+        },
+      ];
+      returns: {
+        vec: {
+          defined: {
+            name: "historicalPrice";
+          };
+        };
+      };
+    },
     {
       name: "price";
       discriminator: [129, 85, 239, 229, 61, 225, 218, 20];
@@ -51,8 +259,8 @@ export type RedstoneSolanaPriceAdapter = {
       ];
     },
     {
-      name: "timestamp";
-      discriminator: [254, 134, 231, 242, 239, 5, 62, 108];
+      name: "priceChecked";
+      discriminator: [241, 83, 175, 113, 202, 216, 69, 120];
       accounts: [
         {
           name: "priceAccount";
@@ -65,27 +273,96 @@ export type RedstoneSolanaPriceAdapter = {
             array: ["u8", 32];
           };
         },
+        {
+          name: "maxAgeMs";
+          type: "u64";
+        },
       ];
-      returns: "u64";
+      returns: {
+        array: ["u8", 32];
+      };
     },
     {
-      name: "uniqueSignersCount";
-      discriminator: [101, 24, 86, 157, 116, 46, 226, 31];
+      name: "priceTwap";
+      discriminator: [38, 72, 174, 52, 145, 139, 157, 156];
+      accounts: [
+        {
+          name: "priceHistory";
+        },
+      ];
+      args: [
+        {
+          name: "feedId";
+          type: {
+            array: ["u8", 32];
+          };
+        },
+        {
+          name: "windowMs";
+          type: "u64";
+        },
+      ];
+      returns: {
+        array: ["u8", 32];
+      };
+    },
+    {
+      name: "readDerivedPrice";
+      discriminator: [249, 228, 55, 128, 143, 217, 130, 189];
       accounts: [];
-      args: [];
-      returns: "u8";
+      args: [
+        {
+          name: "formula";
+          type: {
+            defined: {
+              name: "derivedPriceFormula";
+            };
+          };
+        },
+      ];
+      returns: {
+        defined: {
+          name: "derivedPrice";
+        };
+      };
     },
     {
-      name: "writePrice";
-      discriminator: [16, 186, 120, 224, 118, 178, 161, 152];
+      name: "readPriceScaled";
+      discriminator: [229, 117, 12, 145, 43, 233, 96, 118];
       accounts: [
         {
-          name: "user";
+          name: "priceAccount";
+        },
+      ];
+      args: [
+        {
+          name: "feedId";
+          type: {
+            array: ["u8", 32];
+          };
+        },
+      ];
+      returns: {
+        defined: {
+          name: "scaledPrice";
+        };
+      };
+    },
+    {
+      name: "reallocPriceAccount";
+      discriminator: [42, 137, 141, 234, 152, 156, 111, 71];
+      accounts: [
+        {
+          name: "payer";
           writable: true;
           signer: true;
         },
         {
           name: "priceAccount";
+          docs: [
+            "CHECK: the account can't be deserialized as `PriceData` before it's resized,",
+            "so its discriminator is checked in the instruction",
+          ];
           writable: true;
         },
         {
@@ -100,31 +377,884 @@ export type RedstoneSolanaPriceAdapter = {
             array: ["u8", 32];
           };
         },
-        {
-          name: "payload";
-          type: "bytes";
-        },
       ];
     },
-  ];
-  accounts: [
     {
-      name: "priceData";
-      discriminator: [232, 113, 193, 231, 133, 209, 206, 154];
+      name: "setFeedAllowed";
+      discriminator: [80, 98, 187, 88, 179, 139, 56, 208];
+      accounts: [
+        {
+          name: "admin";
+          signer: true;
+          relations: ["configAccount"];
+        },
+        {
+          name: "configAccount";
+          writable: true;
+        },
+      ];
+      args: [
+        {
+          name: "feedId";
+          type: {
+            array: ["u8", 32];
+          };
+        },
+        {
+          name: "allowed";
+          type: "bool";
+        },
+      ];
     },
-  ];
-  types: [
     {
-      name: "priceData";
-      type: {
-        kind: "struct";
-        fields: [
-          {
-            name: "feedId";
-            type: {
-              array: ["u8", 32];
-            };
-          },
+      name: "setFeedMetadata";
+      discriminator: [127, 0, 181, 107, 86, 157, 158, 183];
+      accounts: [
+        {
+          name: "admin";
+          writable: true;
+          signer: true;
+          relations: ["configAccount"];
+        },
+        {
+          name: "configAccount";
+        },
+        {
+          name: "priceAccount";
+          writable: true;
+        },
+        {
+          name: "systemProgram";
+          address: "11111111111111111111111111111111";
+        },
+      ];
+      args: [
+        {
+          name: "feedId";
+          type: {
+            array: ["u8", 32];
+          };
+        },
+        {
+          name: "metadata";
+          type: {
+            defined: {
+              name: "feedMetadata";
+            };
+          };
+        },
+      ];
+    },
+    {
+      name: "setFeedPaused";
+      discriminator: [134, 92, 182, 59, 70, 187, 171, 11];
+      accounts: [
+        {
+          name: "authority";
+          signer: true;
+        },
+        {
+          name: "configAccount";
+        },
+        {
+          name: "priceAccount";
+          writable: true;
+        },
+      ];
+      args: [
+        {
+          name: "feedId";
+          type: {
+            array: ["u8", 32];
+          };
+        },
+        {
+          name: "paused";
+          type: "bool";
+        },
+      ];
+    },
+    {
+      name: "setPaused";
+      discriminator: [91, 60, 125, 192, 176, 225, 166, 218];
+      accounts: [
+        {
+          name: "authority";
+          signer: true;
+        },
+        {
+          name: "configAccount";
+          writable: true;
+        },
+      ];
+      args: [
+        {
+          name: "paused";
+          type: "bool";
+        },
+      ];
+    },
+    {
+      name: "setPauser";
+      discriminator: [22, 198, 152, 61, 2, 13, 145, 238];
+      accounts: [
+        {
+          name: "admin";
+          signer: true;
+          relations: ["configAccount"];
+        },
+        {
+          name: "configAccount";
+          writable: true;
+        },
+      ];
+      args: [
+        {
+          name: "pauser";
+          type: "pubkey";
+        },
+      ];
+    },
+    {
+      name: "setUpdaterAllowListEnabled";
+      discriminator: [238, 28, 106, 129, 79, 187, 60, 169];
+      accounts: [
+        {
+          name: "admin";
+          signer: true;
+          relations: ["configAccount"];
+        },
+        {
+          name: "configAccount";
+          writable: true;
+        },
+      ];
+      args: [
+        {
+          name: "enabled";
+          type: "bool";
+        },
+      ];
+    },
+    {
+      name: "setUpdaterAllowed";
+      discriminator: [235, 13, 74, 233, 11, 80, 113, 18];
+      accounts: [
+        {
+          name: "admin";
+          signer: true;
+          relations: ["configAccount"];
+        },
+        {
+          name: "configAccount";
+          writable: true;
+        },
+      ];
+      args: [
+        {
+          name: "updater";
+          type: "pubkey";
+        },
+        {
+          name: "allowed";
+          type: "bool";
+        },
+      ];
+    },
+    {
+      name: "timestamp";
+      discriminator: [254, 134, 231, 242, 239, 5, 62, 108];
+      accounts: [
+        {
+          name: "priceAccount";
+        },
+      ];
+      args: [
+        {
+          name: "feedId";
+          type: {
+            array: ["u8", 32];
+          };
+        },
+      ];
+      returns: "u64";
+    },
+    {
+      name: "transferAdmin";
+      discriminator: [42, 242, 66, 106, 228, 10, 111, 156];
+      accounts: [
+        {
+          name: "admin";
+          signer: true;
+          relations: ["configAccount"];
+        },
+        {
+          name: "configAccount";
+          writable: true;
+        },
+      ];
+      args: [
+        {
+          name: "newAdmin";
+          type: "pubkey";
+        },
+      ];
+    },
+    {
+      name: "uniqueSignersCount";
+      discriminator: [101, 24, 86, 157, 116, 46, 226, 31];
+      accounts: [
+        {
+          name: "configAccount";
+        },
+      ];
+      args: [];
+      returns: "u8";
+    },
+    {
+      name: "updateConfig";
+      discriminator: [29, 158, 252, 191, 10, 83, 219, 99];
+      accounts: [
+        {
+          name: "admin";
+          signer: true;
+          relations: ["configAccount"];
+        },
+        {
+          name: "configAccount";
+          writable: true;
+        },
+      ];
+      args: [
+        {
+          name: "params";
+          type: {
+            defined: {
+              name: "configParams";
+            };
+          };
+        },
+      ];
+    },
+    {
+      name: "updateScaledPrice";
+      discriminator: [30, 164, 121, 209, 203, 194, 242, 227];
+      accounts: [
+        {
+          name: "user";
+          writable: true;
+          signer: true;
+        },
+        {
+          name: "priceAccount";
+        },
+        {
+          name: "scaledPriceAccount";
+          writable: true;
+        },
+        {
+          name: "systemProgram";
+          address: "11111111111111111111111111111111";
+        },
+      ];
+      args: [
+        {
+          name: "feedId";
+          type: {
+            array: ["u8", 32];
+          };
+        },
+      ];
+    },
+    {
+      name: "writePrice";
+      discriminator: [16, 186, 120, 224, 118, 178, 161, 152];
+      accounts: [
+        {
+          name: "user";
+          writable: true;
+          signer: true;
+        },
+        {
+          name: "priceAccount";
+          writable: true;
+        },
+        {
+          name: "configAccount";
+        },
+        {
+          name: "systemProgram";
+          address: "11111111111111111111111111111111";
+        },
+      ];
+      args: [
+        {
+          name: "feedId";
+          type: {
+            array: ["u8", 32];
+          };
+        },
+        {
+          name: "payload";
+          type: "bytes";
+        },
+      ];
+    },
+    {
+      name: "writePrices";
+      discriminator: [110, 0, 74, 164, 129, 112, 155, 133];
+      accounts: [
+        {
+          name: "user";
+          writable: true;
+          signer: true;
+        },
+        {
+          name: "configAccount";
+        },
+        {
+          name: "systemProgram";
+          address: "11111111111111111111111111111111";
+        },
+      ];
+      args: [
+        {
+          name: "feedIds";
+          type: {
+            vec: {
+              array: ["u8", 32];
+            };
+          };
+        },
+        {
+          name: "payload";
+          type: "bytes";
+        },
+      ];
+    },
+    {
+      name: "writePricesFromBuffer";
+      discriminator: [96, 211, 238, 229, 129, 24, 72, 242];
+      accounts: [
+        {
+          name: "user";
+          writable: true;
+          signer: true;
+        },
+        {
+          name: "configAccount";
+        },
+        {
+          name: "payloadBuffer";
+          writable: true;
+        },
+        {
+          name: "systemProgram";
+          address: "11111111111111111111111111111111";
+        },
+      ];
+      args: [
+        {
+          name: "bufferId";
+          type: "u64";
+        },
+        {
+          name: "feedIds";
+          type: {
+            vec: {
+              array: ["u8", 32];
+            };
+          };
+        },
+        {
+          name: "payloadHash";
+          type: {
+            array: ["u8", 32];
+          };
+        },
+      ];
+    },
+  ];
+  accounts: [
+    {
+      name: "configAccount";
+      discriminator: [189, 255, 97, 70, 186, 189, 24, 102];
+    },
+    {
+      name: "payloadBuffer";
+      discriminator: [73, 163, 195, 241, 185, 201, 219, 229];
+    },
+    {
+      name: "priceData";
+      discriminator: [232, 113, 193, 231, 133, 209, 206, 154];
+    },
+    {
+      name: "priceHistory";
+      discriminator: [38, 241, 40, 19, 42, 228, 93, 152];
+    },
+    {
+      name: "scaledPriceData";
+      discriminator: [69, 176, 71, 174, 195, 196, 47, 20];
+    },
+  ];
+  events: [
+    {
+      name: "priceUpdateRejected";
+      discriminator: [187, 218, 235, 250, 110, 155, 205, 39];
+    },
+    {
+      name: "priceUpdated";
+      discriminator: [154, 72, 87, 150, 246, 230, 23, 217];
+    },
+  ];
+  errors: [
+    {
+      code: 6000;
+      name: "priceAccountCountMismatch";
+      msg: "Number of price accounts doesn't match the number of feed ids";
+    },
+    {
+      code: 6001;
+      name: "invalidPriceAccount";
+      msg: "Price account address doesn't match the feed id";
+    },
+    {
+      code: 6002;
+      name: "priceAccountNotWritable";
+      msg: "Price account must be writable";
+    },
+    {
+      code: 6003;
+      name: "missingFeedValue";
+      msg: "Feed value is missing in the payload";
+    },
+    {
+      code: 6004;
+      name: "notAdmin";
+      msg: "Signer is not the admin";
+    },
+    {
+      code: 6005;
+      name: "notPendingAdmin";
+      msg: "Signer is not the pending admin";
+    },
+    {
+      code: 6006;
+      name: "tooManySigners";
+      msg: "Too many signers";
+    },
+    {
+      code: 6007;
+      name: "tooManyTrustedUpdaters";
+      msg: "Too many trusted updaters";
+    },
+    {
+      code: 6008;
+      name: "priceOverflow";
+      msg: "Price doesn't fit in i64";
+    },
+    {
+      code: 6009;
+      name: "priceNotInitialized";
+      msg: "Price has never been written";
+    },
+    {
+      code: 6010;
+      name: "stalePrice";
+      msg: "Price is older than the allowed max age";
+    },
+    {
+      code: 6011;
+      name: "tooManyHistoryEntries";
+      msg: "Too many price history entries requested";
+    },
+    {
+      code: 6012;
+      name: "invalidPriceHistoryAccount";
+      msg: "Price history account doesn't match the feed id";
+    },
+    {
+      code: 6013;
+      name: "feedNotAllowed";
+      msg: "Feed is not allowed to be initialized";
+    },
+    {
+      code: 6014;
+      name: "tooManyAllowedFeeds";
+      msg: "Too many allowed feeds";
+    },
+    {
+      code: 6015;
+      name: "invalidPriceAccountData";
+      msg: "Account is not a price account";
+    },
+    {
+      code: 6016;
+      name: "updateNotRequired";
+      msg: "Price is neither past the heartbeat nor beyond the deviation threshold";
+    },
+    {
+      code: 6017;
+      name: "invalidPrecompileInstruction";
+      msg: "Secp256k1 precompile instruction points outside of the transaction data";
+    },
+    {
+      code: 6018;
+      name: "payloadBufferTooLarge";
+      msg: "Payload buffer capacity is too large";
+    },
+    {
+      code: 6019;
+      name: "payloadChunkOutOfOrder";
+      msg: "Payload chunk doesn't start at the end of the buffered data";
+    },
+    {
+      code: 6020;
+      name: "payloadBufferOverflow";
+      msg: "Payload chunk exceeds the buffer capacity";
+    },
+    {
+      code: 6021;
+      name: "payloadHashMismatch";
+      msg: "Buffered payload doesn't match the hash";
+    },
+    {
+      code: 6022;
+      name: "notPauser";
+      msg: "Signer is neither the pauser nor the admin";
+    },
+    {
+      code: 6023;
+      name: "paused";
+      msg: "Writes are paused";
+    },
+    {
+      code: 6024;
+      name: "feedPaused";
+      msg: "Writes of the feed are paused";
+    },
+    {
+      code: 6025;
+      name: "unauthorizedUpdater";
+      msg: "Updater is not allowed to write";
+    },
+    {
+      code: 6026;
+      name: "tooManyUpdaters";
+      msg: "Too many updaters";
+    },
+    {
+      code: 6027;
+      name: "invalidDerivedPriceFormula";
+      msg: "Derived price formula has no components or too many of them";
+    },
+    {
+      code: 6028;
+      name: "derivedPriceDivisionByZero";
+      msg: "Derived price formula divides by a zero price";
+    },
+    {
+      code: 6029;
+      name: "duplicateFeedId";
+      msg: "Feed id is passed more than once";
+    },
+    {
+      code: 6030;
+      name: "invalidScaledPriceAccount";
+      msg: "Scaled price account doesn't match the feed id";
+    },
+  ];
+  types: [
+    {
+      name: "configAccount";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "admin";
+            type: "pubkey";
+          },
+          {
+            name: "pendingAdmin";
+            type: {
+              option: "pubkey";
+            };
+          },
+          {
+            name: "signerCountThreshold";
+            type: "u8";
+          },
+          {
+            name: "signers";
+            type: {
+              vec: {
+                array: ["u8", 20];
+              };
+            };
+          },
+          {
+            name: "trustedUpdaters";
+            type: {
+              vec: "pubkey";
+            };
+          },
+          {
+            name: "maxTimestampDelayMs";
+            type: "u64";
+          },
+          {
+            name: "maxTimestampAheadMs";
+            type: "u64";
+          },
+          {
+            name: "minIntervalBetweenUpdatesMs";
+            type: "u64";
+          },
+          {
+            name: "allowedFeedIds";
+            docs: ["Feeds whose `PriceData` accounts can be created by the write instructions."];
+            type: {
+              vec: {
+                array: ["u8", 32];
+              };
+            };
+          },
+          {
+            name: "pauser";
+            docs: ["Can pause and unpause the writes, as well as the admin."];
+            type: "pubkey";
+          },
+          {
+            name: "paused";
+            type: "bool";
+          },
+          {
+            name: "updaterAllowListEnabled";
+            docs: ["Restricts the writes to the `updaters` and the `trusted_updaters`."];
+            type: "bool";
+          },
+          {
+            name: "updaters";
+            type: {
+              vec: "pubkey";
+            };
+          },
+        ];
+      };
+    },
+    {
+      name: "configParams";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "signerCountThreshold";
+            type: "u8";
+          },
+          {
+            name: "signers";
+            type: {
+              vec: {
+                array: ["u8", 20];
+              };
+            };
+          },
+          {
+            name: "trustedUpdaters";
+            type: {
+              vec: "pubkey";
+            };
+          },
+          {
+            name: "maxTimestampDelayMs";
+            type: "u64";
+          },
+          {
+            name: "maxTimestampAheadMs";
+            type: "u64";
+          },
+          {
+            name: "minIntervalBetweenUpdatesMs";
+            type: "u64";
+          },
+        ];
+      };
+    },
+    {
+      name: "derivedPrice";
+      docs: [
+        "Result of the [`DerivedPriceFormula`], with the package timestamp of its oldest component.",
+      ];
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "value";
+            type: {
+              array: ["u8", 32];
+            };
+          },
+          {
+            name: "decimals";
+            type: "u8";
+          },
+          {
+            name: "timestamp";
+            type: "u64";
+          },
+        ];
+      };
+    },
+    {
+      name: "derivedPriceComponent";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "feedId";
+            type: {
+              array: ["u8", 32];
+            };
+          },
+          {
+            name: "operation";
+            type: {
+              defined: {
+                name: "derivedPriceOperation";
+              };
+            };
+          },
+        ];
+      };
+    },
+    {
+      name: "derivedPriceFormula";
+      docs: [
+        "Price computed on read from the values of the `components`, applied one after another",
+        "starting from 1, e.g. `stSOL/SOL * SOL/USD`, with `decimals` decimals of the result.",
+      ];
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "components";
+            type: {
+              vec: {
+                defined: {
+                  name: "derivedPriceComponent";
+                };
+              };
+            };
+          },
+          {
+            name: "decimals";
+            type: "u8";
+          },
+        ];
+      };
+    },
+    {
+      name: "derivedPriceOperation";
+      type: {
+        kind: "enum";
+        variants: [
+          {
+            name: "multiply";
+          },
+          {
+            name: "divide";
+          },
+        ];
+      };
+    },
+    {
+      name: "feedMetadata";
+      docs: [
+        "Per-feed configuration set by the admin, stored in the formerly reserved bytes of [`PriceData`].",
+        "The zeroed metadata of the existing accounts keeps the former behavior.",
+      ];
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "heartbeatMs";
+            docs: [
+              "Time between the package timestamps after which an update is always accepted, 0 if not checked.",
+            ];
+            type: "u64";
+          },
+          {
+            name: "deviationThresholdBps";
+            docs: [
+              "Price change in basis points for which an update is accepted before the heartbeat, 0 if not checked.",
+            ];
+            type: "u16";
+          },
+          {
+            name: "minIntervalBetweenUpdatesMs";
+            docs: ["Overrides the `min_interval_between_updates_ms` of the config when not 0."];
+            type: "u64";
+          },
+          {
+            name: "decimals";
+            docs: [
+              "Decimals of the feed values when they differ from [`REDSTONE_DECIMALS_EXP`], 0 for the default.",
+            ];
+            type: "u8";
+          },
+        ];
+      };
+    },
+    {
+      name: "historicalPrice";
+      docs: ["[`PriceHistoryEntry`] as returned by the read instructions."];
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "value";
+            type: {
+              array: ["u8", 32];
+            };
+          },
+          {
+            name: "timestamp";
+            type: "u64";
+          },
+          {
+            name: "writeSlot";
+            type: "u64";
+          },
+        ];
+      };
+    },
+    {
+      name: "payloadBuffer";
+      docs: [
+        "Payload too large for a single transaction, filled in chunks by `append_payload_chunk`",
+        "and consumed by `write_prices_from_buffer`.",
+      ];
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "data";
+            type: "bytes";
+          },
+        ];
+      };
+    },
+    {
+      name: "priceData";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "feedId";
+            type: {
+              array: ["u8", 32];
+            };
+          },
           {
             name: "value";
             type: {
@@ -149,12 +1279,221 @@ export type RedstoneSolanaPriceAdapter = {
             name: "decimals";
             type: "u8";
           },
+          {
+            name: "metadata";
+            type: {
+              defined: {
+                name: "feedMetadata";
+              };
+            };
+          },
+          {
+            name: "paused";
+            docs: ["Set by the pauser to reject the writes of the feed."];
+            type: "bool";
+          },
           {
             name: "reserved";
             type: {
-              array: ["u8", 64];
+              array: ["u8", 44];
+            };
+          },
+        ];
+      };
+    },
+    {
+      name: "priceHistory";
+      docs: ["Ring buffer of the last [`PRICE_HISTORY_CAPACITY`] values written for the feed."];
+      serialization: "bytemuck";
+      repr: {
+        kind: "c";
+      };
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "feedId";
+            type: {
+              array: ["u8", 32];
+            };
+          },
+          {
+            name: "head";
+            docs: ["Index the next entry is written at."];
+            type: "u64";
+          },
+          {
+            name: "len";
+            type: "u64";
+          },
+          {
+            name: "entries";
+            type: {
+              array: [
+                {
+                  defined: {
+                    name: "priceHistoryEntry";
+                  };
+                },
+                128,
+              ];
+            };
+          },
+        ];
+      };
+    },
+    {
+      name: "priceHistoryEntry";
+      serialization: "bytemuck";
+      repr: {
+        kind: "c";
+      };
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "value";
+            type: {
+              array: ["u8", 32];
+            };
+          },
+          {
+            name: "timestamp";
+            type: "u64";
+          },
+          {
+            name: "writeSlot";
+            type: "u64";
+          },
+        ];
+      };
+    },
+    {
+      name: "priceUpdateRejected";
+      docs: [
+        "Emitted for every feed skipped by the batch writes, with the `error_code`",
+        "the same update would fail with when written alone.",
+      ];
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "feedId";
+            type: {
+              array: ["u8", 32];
+            };
+          },
+          {
+            name: "packageTimestamp";
+            type: "u64";
+          },
+          {
+            name: "updater";
+            type: "pubkey";
+          },
+          {
+            name: "errorCode";
+            type: "u32";
+          },
+        ];
+      };
+    },
+    {
+      name: "priceUpdated";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "feedId";
+            type: {
+              array: ["u8", 32];
+            };
+          },
+          {
+            name: "value";
+            type: {
+              array: ["u8", 32];
+            };
+          },
+          {
+            name: "timestamp";
+            type: "u64";
+          },
+          {
+            name: "writeSlot";
+            type: "u64";
+          },
+          {
+            name: "updater";
+            type: "pubkey";
+          },
+        ];
+      };
+    },
+    {
+      name: "scaledPrice";
+      docs: [
+        "The price in the layout used by Pyth and Switchboard integrators:",
+        "`price * 10^expo`, published at `publish_time` given in seconds.",
+        "RedStone doesn't provide a confidence interval, so `conf` is always 0.",
+      ];
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "price";
+            type: "i64";
+          },
+          {
+            name: "conf";
+            type: "u64";
+          },
+          {
+            name: "expo";
+            type: "i32";
+          },
+          {
+            name: "publishTime";
+            type: "i64";
+          },
+        ];
+      };
+    },
+    {
+      name: "scaledPriceData";
+      docs: [
+        "Mirror of [`PriceData`] kept in the [`ScaledPrice`] layout,",
+        "for programs reading the price accounts directly.",
+      ];
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "feedId";
+            type: {
+              array: ["u8", 32];
             };
           },
+          {
+            name: "price";
+            type: "i64";
+          },
+          {
+            name: "conf";
+            type: "u64";
+          },
+          {
+            name: "expo";
+            type: "i32";
+          },
+          {
+            name: "publishTime";
+            type: "i64";
+          },
+          {
+            name: "postedSlot";
+            type: "u64";
+          },
         ];
       };
     },
@@ -4,7 +4,7 @@ import { PublicKey } from "@solana/web3.js";
 import { RedstoneSolanaPriceAdapter as PriceAdapter } from "../_autogenerated/redstone_solana_price_adapter";
 import idl from "../_autogenerated/redstone_solana_price_adapter.json";
 import { SolanaClient } from "../client/SolanaClient";
-import { makeFeedIdBytes, makePriceSeed } from "../utils";
+import { makeConfigSeed, makeFeedIdBytes, makePriceSeed } from "../utils";
 import { decodePriceData, PriceData } from "./PriceData";
 
 export class PriceAdapterContract {
@@ -20,6 +20,10 @@ export class PriceAdapterContract {
     this.program = new Program(priceAdapter, provider);
   }
 
+  static getConfigAccount(programId: PublicKey): PublicKey {
+    return web3.PublicKey.findProgramAddressSync([makeConfigSeed()], programId)[0];
+  }
+
   private static getPriceDataAccount(programId: PublicKey, feedId: string): PublicKey {
     return web3.PublicKey.findProgramAddressSync(
       [makePriceSeed(), makeFeedIdBytes(feedId)],
@@ -69,6 +73,18 @@ export class PriceAdapterContract {
       .accountsStrict({
         user,
         priceAccount: PriceAdapterContract.getPriceDataAccount(this.program.programId, feedId),
+        configAccount: PriceAdapterContract.getConfigAccount(this.program.programId),
+        systemProgram: web3.SystemProgram.programId,
+      })
+      .instruction();
+  }
+
+  async initializeConfigTx(payer: PublicKey) {
+    return await this.program.methods
+      .initializeConfig()
+      .accountsStrict({
+        payer,
+        configAccount: PriceAdapterContract.getConfigAccount(this.program.programId),
         systemProgram: web3.SystemProgram.programId,
       })
       .instruction();
@@ -76,7 +92,9 @@ export class PriceAdapterContract {
 
   async getUniqueSignerThreshold(slot?: number): Promise<number> {
     return await this.client.viewMethod(
-      this.program.methods.uniqueSignersCount(),
+      this.program.methods.uniqueSignersCount().accountsStrict({
+        configAccount: PriceAdapterContract.getConfigAccount(this.program.programId),
+      }),
       slot,
       "getUniqueSignerThreshold"
     );
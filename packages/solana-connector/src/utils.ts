@@ -21,6 +21,10 @@ export const makePriceSeed = () => {
   return Buffer.from("price".padEnd(32, "\0"));
 };
 
+export const makeConfigSeed = () => {
+  return Buffer.from("config".padEnd(32, "\0"));
+};
+
 const BYTE_LENGTHS = {
   PRIVATE_KEY: 32,
   SECRET_KEY: 64,
@@ -1,7 +1,16 @@
 import {} from "@redstone-finance/sdk";
-import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import {
+  Keypair,
+  LAMPORTS_PER_SOL,
+  PublicKey,
+  SystemProgram,
+  Transaction,
+  TransactionInstruction,
+} from "@solana/web3.js";
 import { LiteSVM } from "litesvm";
 import path from "path";
+import idl from "../src/_autogenerated/redstone_solana_price_adapter.json";
+import { PriceAdapterContract } from "../src/price_adapter/PriceAdapterContract";
 
 function programPath() {
   return path.join(__dirname, "../solana/target/deploy/redstone_solana_price_adapter.so");
@@ -13,6 +22,33 @@ function publicToDummyKeypair(publicKey: Uint8Array) {
   return new Keypair({ publicKey, secretKey });
 }
 
+function initializeConfig(svm: LiteSVM, programId: PublicKey, payer: Keypair) {
+  const initializeConfigIdl = idl.instructions.find(({ name }) => name === "initialize_config");
+  if (!initializeConfigIdl) {
+    throw new Error("initialize_config is missing in the IDL");
+  }
+
+  const instruction = new TransactionInstruction({
+    programId,
+    keys: [
+      { pubkey: payer.publicKey, isSigner: true, isWritable: true },
+      {
+        pubkey: PriceAdapterContract.getConfigAccount(programId),
+        isSigner: false,
+        isWritable: true,
+      },
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+    ],
+    data: Buffer.from(initializeConfigIdl.discriminator),
+  });
+
+  const tx = new Transaction().add(instruction);
+  tx.recentBlockhash = svm.latestBlockhash();
+  tx.feePayer = payer.publicKey;
+  tx.sign(payer);
+  svm.sendTransaction(tx);
+}
+
 export function setUpEnv() {
   const svm = new LiteSVM().withBlockhashCheck(false).withSigverify(false);
 
@@ -29,6 +65,7 @@ export function setUpEnv() {
 
   svm.airdrop(trustedSigner.publicKey, BigInt(LAMPORTS_PER_SOL));
   svm.airdrop(untrustedSigner.publicKey, BigInt(LAMPORTS_PER_SOL));
+  initializeConfig(svm, programId, untrustedSigner);
 
   return {
     svm,
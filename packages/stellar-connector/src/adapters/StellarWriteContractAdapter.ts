@@ -86,6 +86,7 @@ async function prepareCallArgs(
   paramsProvider: ContractParamsProvider,
   metadataTimestamp = Date.now()
 ) {
+  const serviceIdScVal = XdrUtils.stringToScVal(paramsProvider.requestParams.dataServiceId);
   const feedIdsScVal = XdrUtils.mapArrayToScVec(
     paramsProvider.getDataFeedIds(),
     XdrUtils.stringToScVal
@@ -99,5 +100,5 @@ async function prepareCallArgs(
     })
   );
 
-  return [feedIdsScVal, payloadScVal];
+  return [serviceIdScVal, feedIdsScVal, payloadScVal];
 }
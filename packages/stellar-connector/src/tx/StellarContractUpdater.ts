@@ -33,6 +33,7 @@ export class StellarContractUpdater
   }
 
   private async prepareCallArgs(paramsProvider: ContractParamsProvider, metadataTimestamp: number) {
+    const serviceIdScVal = XdrUtils.stringToScVal(paramsProvider.requestParams.dataServiceId);
     const feedIdsScVal = XdrUtils.mapArrayToScVec(
       paramsProvider.getDataFeedIds(),
       XdrUtils.stringToScVal
@@ -46,6 +47,6 @@ export class StellarContractUpdater
       })
     );
 
-    return [feedIdsScVal, payloadScVal];
+    return [serviceIdScVal, feedIdsScVal, payloadScVal];
   }
 }
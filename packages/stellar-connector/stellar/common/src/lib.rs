@@ -7,7 +7,7 @@ pub mod upgradable;
 use soroban_sdk::{
     contracttype,
     xdr::{ScErrorCode, ScErrorType},
-    ConversionError, Error, InvokeError, U256,
+    Bytes, ConversionError, Error, InvokeError, U256,
 };
 
 #[derive(Debug, Clone)]
@@ -18,6 +18,27 @@ pub struct PriceData {
     pub write_timestamp: u64,
 }
 
+#[derive(Debug, Clone)]
+#[contracttype]
+pub struct ValueData {
+    pub value: Bytes,
+    pub package_timestamp: u64,
+    pub write_timestamp: u64,
+}
+
+/// Describes how the 32-byte value of a RedStone data point is interpreted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[contracttype]
+#[repr(u32)]
+pub enum ValueType {
+    /// Unsigned big-endian number, stored as `PriceData`.
+    Price = 0,
+    /// Signed big-endian two's complement number, stored as `ValueData`.
+    Signed = 1,
+    /// Raw bytes, stored as `ValueData`.
+    Bytes = 2,
+}
+
 const CONTRACT_TTL_SECS: u32 = 7 * 24 * 60 * 60;
 
 pub const CONTRACT_TTL_THRESHOLD_LEDGERS: u32 = CONTRACT_TTL_SECS / 5;
@@ -26,6 +26,8 @@ pub struct Config {
     pub min_interval_between_updates_ms: u64,
 }
 
+pub const PRIMARY_DATA_SERVICE_ID: &str = "redstone-primary-prod";
+
 pub const DATA_STALENESS: TimestampMillis = TimestampMillis::from_millis(30 * 60 * 60 * 1000);
 
 pub const FEED_TTL_SECS: u32 = 2 * 24 * 60 * 60;
@@ -0,0 +1,107 @@
+use alloc::vec::Vec as AllocVec;
+
+use redstone::{
+    contract::verification::verify_signers_config, soroban::SorobanCrypto, ConfigFactory,
+    SignerAddress,
+};
+use soroban_sdk::{contracttype, BytesN, Env, Error, String, Vec};
+
+use crate::{
+    config::{PRIMARY_DATA_SERVICE_ID, STELLAR_CONFIG},
+    error_from_redstone_error, EMPTY_ALLOWED_FEEDS_CODE, INVALID_SIGNER_COUNT_THRESHOLD_CODE,
+};
+
+/// Set of signers producing data for a RedStone data service.
+///
+/// Feeds are stored under the same keys for every data service, so each data
+/// service other than the primary one must list the feeds it's allowed to
+/// write in `allowed_feeds`. The empty list of the primary data service allows
+/// every feed.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DataService {
+    pub signers: Vec<BytesN<20>>,
+    pub signer_count_threshold: u32,
+    pub allowed_feeds: Vec<String>,
+}
+
+impl DataService {
+    /// The built-in data service backed by `STELLAR_CONFIG`.
+    pub fn primary(env: &Env) -> Self {
+        let signers = STELLAR_CONFIG
+            .signers
+            .iter()
+            .map(|signer| BytesN::from_array(env, signer));
+
+        Self {
+            signers: Vec::from_iter(env, signers),
+            signer_count_threshold: STELLAR_CONFIG.signer_count_threshold.into(),
+            allowed_feeds: Vec::new(env),
+        }
+    }
+
+    pub fn is_primary(service_id: &String) -> bool {
+        *service_id == String::from_str(service_id.env(), PRIMARY_DATA_SERVICE_ID)
+    }
+
+    pub fn is_feed_allowed(&self, feed_id: &String) -> bool {
+        self.allowed_feeds.is_empty() || self.allowed_feeds.contains(feed_id)
+    }
+
+    /// Checks the data service before it's stored, so that `config` doesn't
+    /// have to repeat the checks on every payload.
+    pub fn validate(&self) -> Result<(), Error> {
+        if self.allowed_feeds.is_empty() {
+            return Err(Error::from_contract_error(EMPTY_ALLOWED_FEEDS_CODE));
+        }
+
+        let config = self.config()?;
+
+        verify_signers_config(&config.signers, config.signer_count_threshold)
+            .map_err(error_from_redstone_error)
+    }
+
+    pub fn config(&self) -> Result<DataServiceConfig, Error> {
+        let signer_count_threshold = self
+            .signer_count_threshold
+            .try_into()
+            .map_err(|_| Error::from_contract_error(INVALID_SIGNER_COUNT_THRESHOLD_CODE))?;
+        let signers = self
+            .signers
+            .iter()
+            .map(|signer| signer.to_array().to_vec().into())
+            .collect();
+
+        Ok(DataServiceConfig {
+            signer_count_threshold,
+            signers,
+        })
+    }
+}
+
+pub struct DataServiceConfig {
+    signer_count_threshold: u8,
+    signers: AllocVec<SignerAddress>,
+}
+
+impl<'a> ConfigFactory<&'a Env, SorobanCrypto<'a>> for DataServiceConfig {
+    fn signer_count_threshold(&self) -> u8 {
+        self.signer_count_threshold
+    }
+
+    fn redstone_signers(&self) -> AllocVec<SignerAddress> {
+        self.signers.clone()
+    }
+
+    fn max_timestamp_delay_ms(&self) -> u64 {
+        STELLAR_CONFIG.max_timestamp_delay_ms
+    }
+
+    fn max_timestamp_ahead_ms(&self) -> u64 {
+        STELLAR_CONFIG.max_timestamp_ahead_ms
+    }
+
+    fn make_crypto(env: &'a Env) -> SorobanCrypto<'a> {
+        SorobanCrypto::new(env)
+    }
+}
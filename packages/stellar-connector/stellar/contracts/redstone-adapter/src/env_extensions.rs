@@ -1,11 +1,12 @@
 use common::{
-    PriceData, CONTRACT_TTL_EXTEND_TO_LEDGERS, CONTRACT_TTL_THRESHOLD_LEDGERS,
-    MISSING_STORAGE_ENTRY,
+    PriceData, ValueData, ValueType, CONTRACT_TTL_EXTEND_TO_LEDGERS,
+    CONTRACT_TTL_THRESHOLD_LEDGERS, MISSING_STORAGE_ENTRY,
 };
 use soroban_sdk::{Env, Error, String};
 
 use crate::{
     config::{FEED_TTL_EXTEND_TO, FEED_TTL_THRESHOLD},
+    data_service::DataService,
     price_data_storage::PriceDataStorage,
     StorageKey,
 };
@@ -17,6 +18,21 @@ pub trait EnvExt {
     fn get_latest_price_data_for_feed(&self, feed: &String) -> Option<PriceData>;
     fn try_get_latest_price_data_for_feed(&self, feed: &String) -> Result<PriceData, Error>;
     fn save_feed(&self, feed: &String, storage: &PriceDataStorage, latest: &PriceData);
+    fn get_value_type(&self, feed: &String) -> ValueType;
+    fn set_value_type(&self, feed: &String, value_type: ValueType);
+    fn get_latest_value_data_for_feed(&self, feed: &String) -> Option<ValueData>;
+    fn try_get_latest_value_data_for_feed(&self, feed: &String) -> Result<ValueData, Error>;
+    fn save_value_feed(&self, feed: &String, latest: &ValueData);
+    fn has_feed_data(&self, feed: &String) -> bool;
+    fn get_feed_alias(&self, alias: &String) -> Option<String>;
+    fn is_feed_alias_target(&self, feed: &String) -> bool;
+    fn set_feed_alias(&self, alias: &String, feed: &String);
+    fn remove_feed_alias(&self, alias: &String);
+    fn try_get_data_service(&self, service_id: &String) -> Result<DataService, Error>;
+    fn set_data_service(&self, service_id: &String, data_service: &DataService);
+    fn remove_data_service(&self, service_id: &String);
+    fn try_get_feed_data_service(&self, feed: &String) -> Result<String, Error>;
+    fn save_feed_data_service(&self, feed: &String, service_id: &String);
 }
 
 impl EnvExt for Env {
@@ -60,4 +76,145 @@ impl EnvExt for Env {
         db.set(feed, latest);
         db.extend_ttl(feed, FEED_TTL_THRESHOLD, FEED_TTL_EXTEND_TO);
     }
+
+    fn get_value_type(&self, feed: &String) -> ValueType {
+        self.storage()
+            .persistent()
+            .get(&StorageKey::ValueType(feed.clone()))
+            .unwrap_or(ValueType::Price)
+    }
+
+    fn set_value_type(&self, feed: &String, value_type: ValueType) {
+        let db = self.storage().persistent();
+        let value_type_key = StorageKey::ValueType(feed.clone());
+
+        if value_type == ValueType::Price {
+            db.remove(&value_type_key);
+            return;
+        }
+
+        db.set(&value_type_key, &value_type);
+        db.extend_ttl(&value_type_key, FEED_TTL_THRESHOLD, FEED_TTL_EXTEND_TO);
+    }
+
+    fn get_latest_value_data_for_feed(&self, feed: &String) -> Option<ValueData> {
+        self.storage()
+            .persistent()
+            .get(&StorageKey::Value(feed.clone()))
+    }
+
+    fn try_get_latest_value_data_for_feed(&self, feed: &String) -> Result<ValueData, Error> {
+        self.get_latest_value_data_for_feed(feed)
+            .ok_or(MISSING_STORAGE_ENTRY)
+    }
+
+    fn save_value_feed(&self, feed: &String, latest: &ValueData) {
+        let db = self.storage().persistent();
+        let value_key = StorageKey::Value(feed.clone());
+
+        db.set(&value_key, latest);
+        db.extend_ttl(&value_key, FEED_TTL_THRESHOLD, FEED_TTL_EXTEND_TO);
+
+        let value_type_key = StorageKey::ValueType(feed.clone());
+        db.extend_ttl(&value_type_key, FEED_TTL_THRESHOLD, FEED_TTL_EXTEND_TO);
+    }
+
+    fn has_feed_data(&self, feed: &String) -> bool {
+        let db = self.storage().persistent();
+
+        db.has(feed) || db.has(&StorageKey::Value(feed.clone()))
+    }
+
+    fn get_feed_alias(&self, alias: &String) -> Option<String> {
+        let db = self.storage().persistent();
+        let alias_key = StorageKey::Alias(alias.clone());
+        let feed: String = db.get(&alias_key)?;
+
+        db.extend_ttl(&alias_key, FEED_TTL_THRESHOLD, FEED_TTL_EXTEND_TO);
+        db.extend_ttl(
+            &StorageKey::AliasTarget(feed.clone()),
+            FEED_TTL_THRESHOLD,
+            FEED_TTL_EXTEND_TO,
+        );
+
+        Some(feed)
+    }
+
+    fn is_feed_alias_target(&self, feed: &String) -> bool {
+        self.storage()
+            .persistent()
+            .has(&StorageKey::AliasTarget(feed.clone()))
+    }
+
+    fn set_feed_alias(&self, alias: &String, feed: &String) {
+        self.remove_feed_alias(alias);
+
+        let db = self.storage().persistent();
+        let alias_key = StorageKey::Alias(alias.clone());
+        let target_key = StorageKey::AliasTarget(feed.clone());
+        let alias_count: u32 = db.get(&target_key).unwrap_or(0);
+
+        db.set(&alias_key, feed);
+        db.extend_ttl(&alias_key, FEED_TTL_THRESHOLD, FEED_TTL_EXTEND_TO);
+
+        db.set(&target_key, &(alias_count + 1));
+        db.extend_ttl(&target_key, FEED_TTL_THRESHOLD, FEED_TTL_EXTEND_TO);
+    }
+
+    fn remove_feed_alias(&self, alias: &String) {
+        let db = self.storage().persistent();
+        let alias_key = StorageKey::Alias(alias.clone());
+        let Some(feed) = db.get::<_, String>(&alias_key) else {
+            return;
+        };
+        db.remove(&alias_key);
+
+        let target_key = StorageKey::AliasTarget(feed);
+        match db.get::<_, u32>(&target_key).unwrap_or(0) {
+            0 | 1 => db.remove(&target_key),
+            alias_count => db.set(&target_key, &(alias_count - 1)),
+        }
+    }
+
+    fn try_get_data_service(&self, service_id: &String) -> Result<DataService, Error> {
+        if DataService::is_primary(service_id) {
+            return Ok(DataService::primary(self));
+        }
+
+        let db = self.storage().persistent();
+        let service_key = StorageKey::DataService(service_id.clone());
+        let data_service = db.get(&service_key).ok_or(MISSING_STORAGE_ENTRY)?;
+        db.extend_ttl(&service_key, FEED_TTL_THRESHOLD, FEED_TTL_EXTEND_TO);
+
+        Ok(data_service)
+    }
+
+    fn set_data_service(&self, service_id: &String, data_service: &DataService) {
+        let db = self.storage().persistent();
+        let service_key = StorageKey::DataService(service_id.clone());
+
+        db.set(&service_key, data_service);
+        db.extend_ttl(&service_key, FEED_TTL_THRESHOLD, FEED_TTL_EXTEND_TO);
+    }
+
+    fn remove_data_service(&self, service_id: &String) {
+        self.storage()
+            .persistent()
+            .remove(&StorageKey::DataService(service_id.clone()));
+    }
+
+    fn try_get_feed_data_service(&self, feed: &String) -> Result<String, Error> {
+        self.storage()
+            .persistent()
+            .get(&StorageKey::FeedDataService(feed.clone()))
+            .ok_or(MISSING_STORAGE_ENTRY)
+    }
+
+    fn save_feed_data_service(&self, feed: &String, service_id: &String) {
+        let db = self.storage().persistent();
+        let service_key = StorageKey::FeedDataService(feed.clone());
+
+        db.set(&service_key, service_id);
+        db.extend_ttl(&service_key, FEED_TTL_THRESHOLD, FEED_TTL_EXTEND_TO);
+    }
 }
@@ -1,7 +1,8 @@
-use common::PriceData;
+use common::{PriceData, ValueData};
 use soroban_sdk::{contracttype, symbol_short, xdr::ToXdr, Address, Event, Symbol, Vec};
 
 const WRITE_PRICES_TOPIC: Symbol = symbol_short!("REDSTONE");
+const WRITE_VALUES_TOPIC: Symbol = symbol_short!("RS_VALUES");
 
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -19,3 +20,20 @@ impl Event for WritePrices {
         ToXdr::to_xdr(self, env).to_val()
     }
 }
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WriteValues {
+    pub updater: Address,
+    pub updated_values: Vec<ValueData>,
+}
+
+impl Event for WriteValues {
+    fn topics(&self, env: &soroban_sdk::Env) -> Vec<soroban_sdk::Val> {
+        Vec::from_array(env, [WRITE_VALUES_TOPIC.to_val()])
+    }
+
+    fn data(&self, env: &soroban_sdk::Env) -> soroban_sdk::Val {
+        ToXdr::to_xdr(self, env).to_val()
+    }
+}
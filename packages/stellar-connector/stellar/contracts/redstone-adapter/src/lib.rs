@@ -1,7 +1,10 @@
 #![no_std]
 extern crate alloc;
+#[cfg(test)]
+extern crate std;
 
 mod config;
+mod data_service;
 mod env_extensions;
 mod event;
 mod price_data_storage;
@@ -12,6 +15,7 @@ use core::num::NonZero;
 
 use common::{
     ownable::Ownable, redstone_adapter::RedStoneAdapterTrait, upgradable::Upgradable, PriceData,
+    ValueData, ValueType,
 };
 use redstone::{
     contract::verification::{verify_data_staleness, UpdateTimestampVerifier},
@@ -21,17 +25,27 @@ use redstone::{
     ConfigFactory, FeedValue,
 };
 use soroban_sdk::{
-    contract, contractimpl, contracttype, Address, Bytes, BytesN, Env, Error, String, Vec, U256,
+    contract, contractimpl, contracttype, Address, Bytes, BytesN, Env, Error, String, Vec, I256,
+    U256,
 };
 
 use self::config::{DATA_STALENESS, STELLAR_CONFIG};
 use crate::{
+    data_service::DataService,
     env_extensions::EnvExt,
-    event::WritePrices,
-    utils::{feed_to_string, now},
+    event::{WritePrices, WriteValues},
+    utils::{feed_to_canonical_string, normalize_feed_string, now, string_to_feed},
 };
 
 const MISSING_FEED_CODE: u32 = 10;
+const VALUE_TYPE_MISMATCH_CODE: u32 = 11;
+const INVALID_FEED_ID_CODE: u32 = 12;
+const FEED_ID_COLLISION_CODE: u32 = 13;
+const INVALID_FEED_ALIAS_CODE: u32 = 14;
+const RESERVED_DATA_SERVICE_CODE: u32 = 15;
+const FEED_NOT_ALLOWED_CODE: u32 = 16;
+const INVALID_SIGNER_COUNT_THRESHOLD_CODE: u32 = 17;
+const EMPTY_ALLOWED_FEEDS_CODE: u32 = 18;
 const HISTORY_LIMIT: NonZero<u32> = NonZero::new(10).unwrap();
 
 #[contracttype]
@@ -39,6 +53,12 @@ const HISTORY_LIMIT: NonZero<u32> = NonZero::new(10).unwrap();
 enum StorageKey {
     Feed(String),
     HistoryLimit,
+    Value(String),
+    ValueType(String),
+    Alias(String),
+    AliasTarget(String),
+    DataService(String),
+    FeedDataService(String),
 }
 
 #[contract]
@@ -71,65 +91,222 @@ impl RedStoneAdapter {
 
     pub fn get_prices(
         env: &Env,
+        service_id: String,
         feed_ids: Vec<String>,
         payload: Bytes,
     ) -> Result<(u64, Vec<U256>), Error> {
-        let (timestamp, prices) =
-            get_prices_from_payload(env, &feed_ids, &payload).map_err(error_from_redstone_error)?;
+        let service = env.try_get_data_service(&service_id)?;
+        let (timestamp, values) = get_all_values_from_payload(env, &service, &feed_ids, &payload)?;
 
-        if prices.len() != feed_ids.len() {
-            return Err(Error::from_contract_error(MISSING_FEED_CODE));
-        }
+        Ok((timestamp, values_to_prices(env, values)))
+    }
+
+    /// Works like `get_prices`, but additionally stores the verified values as
+    /// if they were passed to `write_prices` by the `updater`.
+    ///
+    /// Feeds are cached only when they pass the same `UpdateTimestampVerifier`
+    /// rules as in `write_prices`. Returns the package timestamp, the values and
+    /// the list of feeds that were cached.
+    ///
+    /// The `updater` must authorize the call, as it decides whether the
+    /// trusted or untrusted rules apply to the cached values.
+    pub fn get_and_cache_prices(
+        env: &Env,
+        updater: Address,
+        service_id: String,
+        feed_ids: Vec<String>,
+        payload: Bytes,
+    ) -> Result<(u64, Vec<U256>, Vec<String>), Error> {
+        updater.require_auth();
+        env.extend_instance_ttl();
+
+        let service = env.try_get_data_service(&service_id)?;
+        let (package_timestamp, values) =
+            get_all_values_from_payload(env, &service, &feed_ids, &payload)?;
+        let cached_feeds =
+            write_values_to_storage(env, updater, &service_id, package_timestamp, &values);
 
         Ok((
-            timestamp,
-            Vec::from_iter(env, prices.into_iter().map(|(_, price)| price)),
+            package_timestamp,
+            values_to_prices(env, values),
+            cached_feeds,
         ))
     }
 
     pub fn write_prices(
         env: &Env,
         updater: Address,
+        service_id: String,
         feed_ids: Vec<String>,
         payload: Bytes,
     ) -> Result<(), Error> {
         updater.require_auth();
         env.extend_instance_ttl();
 
-        let verifier =
-            UpdateTimestampVerifier::verifier(&updater, &STELLAR_CONFIG.trusted_updaters(env));
+        let service = env.try_get_data_service(&service_id)?;
+        let (package_timestamp, values) =
+            get_values_from_payload(env, &service, &feed_ids, &payload)?;
 
-        let (package_timestamp, prices) =
-            get_prices_from_payload(env, &feed_ids, &payload).map_err(error_from_redstone_error)?;
-        let write_timestamp = now(env);
+        write_values_to_storage(env, updater, &service_id, package_timestamp, &values);
 
-        let mut updated_feeds = Vec::new(env);
+        Ok(())
+    }
 
-        for (feed_id, price) in prices.iter() {
-            let price_data = PriceData {
-                price,
-                package_timestamp,
-                write_timestamp: write_timestamp.as_millis(),
-            };
+    /// Registers a data service whose payloads can be passed to `write_prices`,
+    /// `get_prices` and `get_and_cache_prices` under `service_id`.
+    ///
+    /// The primary data service is built into the contract and can't be
+    /// replaced. Other data services must have a non-empty `allowed_feeds` list.
+    pub fn set_data_service(
+        env: &Env,
+        service_id: String,
+        data_service: DataService,
+    ) -> Result<(), Error> {
+        Self::_assert_owner(env)?;
 
-            if update_feed(env, &verifier, &feed_id, &price_data, HISTORY_LIMIT.get()) {
-                updated_feeds.push_back(price_data.clone());
-            }
+        if DataService::is_primary(&service_id) {
+            return Err(Error::from_contract_error(RESERVED_DATA_SERVICE_CODE));
         }
 
-        env.events().publish_event(&WritePrices {
-            updated_feeds,
-            updater,
-        });
+        data_service.validate()?;
+        env.set_data_service(&service_id, &data_service);
+
+        Ok(())
+    }
+
+    pub fn remove_data_service(env: &Env, service_id: String) -> Result<(), Error> {
+        Self::_assert_owner(env)?;
+
+        if DataService::is_primary(&service_id) {
+            return Err(Error::from_contract_error(RESERVED_DATA_SERVICE_CODE));
+        }
+
+        env.remove_data_service(&service_id);
+
+        Ok(())
+    }
+
+    pub fn read_data_service(env: &Env, service_id: String) -> Result<DataService, Error> {
+        env.try_get_data_service(&service_id)
+    }
+
+    /// Returns the id of the data service which produced the latest value of
+    /// `feed_id`.
+    pub fn read_feed_data_service(env: &Env, feed_id: String) -> Result<String, Error> {
+        env.try_get_feed_data_service(&resolve_feed_id(env, &feed_id)?)
+    }
+
+    /// Makes `alias` (e.g. `"ETH/USD"`) resolve to `feed_id` (e.g. `"ETH"`) in
+    /// every call taking feed ids.
+    ///
+    /// Aliases can't be chained and can't shadow a feed which already has data
+    /// stored.
+    pub fn set_feed_alias(env: &Env, alias: String, feed_id: String) -> Result<(), Error> {
+        Self::_assert_owner(env)?;
+
+        let alias = normalize_feed_string(env, &alias)
+            .ok_or(Error::from_contract_error(INVALID_FEED_ALIAS_CODE))?;
+        let feed_id = normalize_feed_string(env, &feed_id)
+            .ok_or(Error::from_contract_error(INVALID_FEED_ID_CODE))?;
+
+        if alias == feed_id
+            || env.get_feed_alias(&feed_id).is_some()
+            || env.is_feed_alias_target(&alias)
+            || env.has_feed_data(&alias)
+        {
+            return Err(Error::from_contract_error(INVALID_FEED_ALIAS_CODE));
+        }
+
+        env.set_feed_alias(&alias, &feed_id);
 
         Ok(())
     }
 
+    pub fn remove_feed_alias(env: &Env, alias: String) -> Result<(), Error> {
+        Self::_assert_owner(env)?;
+
+        let alias = normalize_feed_string(env, &alias)
+            .ok_or(Error::from_contract_error(INVALID_FEED_ALIAS_CODE))?;
+        env.remove_feed_alias(&alias);
+
+        Ok(())
+    }
+
+    pub fn read_feed_alias(env: &Env, alias: String) -> Option<String> {
+        env.get_feed_alias(&normalize_feed_string(env, &alias)?)
+    }
+
+    /// Returns the 32-byte RedStone feed id the `feed_id` string (or alias)
+    /// stands for.
+    pub fn canonical_feed_id(env: &Env, feed_id: String) -> Result<BytesN<32>, Error> {
+        let feed_id = resolve_feed_id(env, &feed_id)?;
+        let feed =
+            string_to_feed(&feed_id).ok_or(Error::from_contract_error(INVALID_FEED_ID_CODE))?;
+
+        Ok(BytesN::from_array(env, &feed.to_array()))
+    }
+
+    /// Sets how the values of `feed_id` are interpreted and stored.
+    ///
+    /// Feeds default to `ValueType::Price`, stored as `PriceData` with history.
+    /// `ValueType::Signed` and `ValueType::Bytes` feeds are stored as the latest
+    /// `ValueData` only and can't be read by the price getters.
+    ///
+    /// Each feed holds a single 32-byte value; multi-value data points are not
+    /// supported.
+    pub fn set_value_type(env: &Env, feed_id: String, value_type: ValueType) -> Result<(), Error> {
+        Self::_assert_owner(env)?;
+
+        let feed_id = resolve_feed_id(env, &feed_id)?;
+        env.set_value_type(&feed_id, value_type);
+
+        Ok(())
+    }
+
+    pub fn read_value_type(env: &Env, feed_id: String) -> Result<ValueType, Error> {
+        Ok(env.get_value_type(&resolve_feed_id(env, &feed_id)?))
+    }
+
+    pub fn read_value_data_for_feed(env: &Env, feed_id: String) -> Result<ValueData, Error> {
+        let feed_id = resolve_feed_id(env, &feed_id)?;
+        let value_data = match env.get_value_type(&feed_id) {
+            ValueType::Price => {
+                let last = env.try_get_latest_price_data_for_feed(&feed_id)?;
+
+                ValueData {
+                    value: last.price.to_be_bytes(),
+                    package_timestamp: last.package_timestamp,
+                    write_timestamp: last.write_timestamp,
+                }
+            },
+            ValueType::Signed | ValueType::Bytes => {
+                env.try_get_latest_value_data_for_feed(&feed_id)?
+            },
+        };
+
+        verify_data_staleness(value_data.write_timestamp.into(), now(env), DATA_STALENESS)
+            .map_err(error_from_redstone_error)?;
+
+        Ok(value_data)
+    }
+
+    pub fn read_signed_value_for_feed(env: &Env, feed_id: String) -> Result<I256, Error> {
+        let feed_id = resolve_feed_id(env, &feed_id)?;
+
+        if env.get_value_type(&feed_id) != ValueType::Signed {
+            return Err(Error::from_contract_error(VALUE_TYPE_MISMATCH_CODE));
+        }
+
+        let value_data = Self::read_value_data_for_feed(env, feed_id)?;
+
+        Ok(I256::from_be_bytes(env, &value_data.value))
+    }
+
     pub fn read_prices(env: &Env, feed_ids: Vec<String>) -> Result<Vec<U256>, Error> {
         let mut prices = Vec::new(env);
 
         for feed_id in feed_ids {
-            let last = env.try_get_latest_price_data_for_feed(&feed_id)?;
+            let last = try_get_latest_price_data(env, &feed_id)?;
             let checked = Self::check_price_data(env, last)?;
             prices.push_back(checked.price);
         }
@@ -138,14 +315,14 @@ impl RedStoneAdapter {
     }
 
     pub fn read_timestamp(env: &Env, feed_id: String) -> Result<u64, Error> {
-        let last = env.try_get_latest_price_data_for_feed(&feed_id)?;
+        let last = try_get_latest_price_data(env, &feed_id)?;
         let checked = Self::check_price_data(env, last)?;
 
         Ok(checked.package_timestamp)
     }
 
     pub fn read_price_data_for_feed(env: &Env, feed_id: String) -> Result<PriceData, Error> {
-        let last = env.try_get_latest_price_data_for_feed(&feed_id)?;
+        let last = try_get_latest_price_data(env, &feed_id)?;
 
         Self::check_price_data(env, last)
     }
@@ -154,7 +331,7 @@ impl RedStoneAdapter {
         let mut price_data = Vec::new(env);
 
         for feed_id in feed_ids {
-            let last = env.try_get_latest_price_data_for_feed(&feed_id)?;
+            let last = try_get_latest_price_data(env, &feed_id)?;
             let checked = Self::check_price_data(env, last)?;
             price_data.push_back(checked);
         }
@@ -167,6 +344,9 @@ impl RedStoneAdapter {
         feed_id: String,
         limit: u32,
     ) -> Result<Vec<PriceData>, Error> {
+        let feed_id = resolve_feed_id(env, &feed_id)?;
+        assert_price_feed(env, &feed_id)?;
+
         let storage = env.get_data_for_feed(&feed_id)?;
         let data = storage.get_all();
 
@@ -198,30 +378,188 @@ impl RedStoneAdapterTrait for RedStoneAdapter {
     }
 }
 
-fn get_prices_from_payload(
+fn get_values_from_payload(
     env: &Env,
+    service: &DataService,
     feed_ids: &Vec<String>,
     payload: &Bytes,
-) -> Result<(u64, Vec<(String, U256)>), RedStoneError> {
+) -> Result<(u64, Vec<(String, Bytes)>), Error> {
+    let feed_ids = normalize_feed_ids(env, feed_ids)?;
+
+    if !feed_ids
+        .iter()
+        .all(|feed_id| service.is_feed_allowed(&feed_id))
+    {
+        return Err(Error::from_contract_error(FEED_NOT_ALLOWED_CODE));
+    }
+
     let feed_ids = feed_ids
         .into_iter()
         .map(|id| ToBytes::to_bytes(&id).into())
         .collect();
     let block_timestamp = now(env);
 
-    let mut config: SorobanRedStoneConfig<'_> =
-        STELLAR_CONFIG.redstone_config(env, feed_ids, block_timestamp)?;
-    let result = process_payload(&mut config, payload.to_alloc_vec())?;
+    let service_config = service.config()?;
+    let mut config: SorobanRedStoneConfig<'_> = service_config
+        .redstone_config(env, feed_ids, block_timestamp)
+        .map_err(error_from_redstone_error)?;
+    let result =
+        process_payload(&mut config, payload.to_alloc_vec()).map_err(error_from_redstone_error)?;
 
-    let mut prices = Vec::new(env);
+    let mut values: Vec<(String, Bytes)> = Vec::new(env);
 
     for FeedValue { value, feed } in result.values {
-        let price = U256::from_be_bytes(env, &Bytes::from_array(env, &value.0));
-        let feed_string = feed_to_string(env, feed);
-        prices.push_back((feed_string, price));
+        let value = Bytes::from_array(env, &value.0);
+        let feed_string = feed_to_canonical_string(env, feed)
+            .ok_or(Error::from_contract_error(INVALID_FEED_ID_CODE))?;
+
+        if values.iter().any(|(id, _)| id == feed_string) {
+            return Err(Error::from_contract_error(FEED_ID_COLLISION_CODE));
+        }
+
+        values.push_back((feed_string, value));
+    }
+
+    Ok((result.timestamp.as_millis(), values))
+}
+
+fn get_all_values_from_payload(
+    env: &Env,
+    service: &DataService,
+    feed_ids: &Vec<String>,
+    payload: &Bytes,
+) -> Result<(u64, Vec<(String, Bytes)>), Error> {
+    let (timestamp, values) = get_values_from_payload(env, service, feed_ids, payload)?;
+
+    if values.len() != feed_ids.len() {
+        return Err(Error::from_contract_error(MISSING_FEED_CODE));
+    }
+
+    Ok((timestamp, values))
+}
+
+/// Resolves aliases and normalizes the requested feed ids, rejecting the ones
+/// which end up pointing at the same feed.
+fn normalize_feed_ids(env: &Env, feed_ids: &Vec<String>) -> Result<Vec<String>, Error> {
+    let mut normalized = Vec::new(env);
+
+    for feed_id in feed_ids.iter() {
+        let feed_id = resolve_feed_id(env, &feed_id)?;
+
+        if normalized.contains(&feed_id) {
+            return Err(Error::from_contract_error(FEED_ID_COLLISION_CODE));
+        }
+
+        normalized.push_back(feed_id);
     }
 
-    Ok((result.timestamp.as_millis(), prices))
+    Ok(normalized)
+}
+
+/// Normalizes `feed_id` and resolves it if it's an alias, so that every call
+/// taking feed ids matches aliases and feeds in the same form.
+fn resolve_feed_id(env: &Env, feed_id: &String) -> Result<String, Error> {
+    let feed_id = normalize_feed_string(env, feed_id)
+        .ok_or(Error::from_contract_error(INVALID_FEED_ID_CODE))?;
+
+    Ok(env.get_feed_alias(&feed_id).unwrap_or(feed_id))
+}
+
+/// Reads the latest `PriceData` of `feed_id`, failing for feeds which don't
+/// store prices.
+fn try_get_latest_price_data(env: &Env, feed_id: &String) -> Result<PriceData, Error> {
+    let feed_id = resolve_feed_id(env, feed_id)?;
+    assert_price_feed(env, &feed_id)?;
+
+    env.try_get_latest_price_data_for_feed(&feed_id)
+}
+
+fn assert_price_feed(env: &Env, feed_id: &String) -> Result<(), Error> {
+    if env.get_value_type(feed_id) != ValueType::Price {
+        return Err(Error::from_contract_error(VALUE_TYPE_MISMATCH_CODE));
+    }
+
+    Ok(())
+}
+
+fn values_to_prices(env: &Env, values: Vec<(String, Bytes)>) -> Vec<U256> {
+    Vec::from_iter(
+        env,
+        values
+            .into_iter()
+            .map(|(_, value)| U256::from_be_bytes(env, &value)),
+    )
+}
+
+fn write_values_to_storage(
+    env: &Env,
+    updater: Address,
+    service_id: &String,
+    package_timestamp: u64,
+    values: &Vec<(String, Bytes)>,
+) -> Vec<String> {
+    let verifier =
+        UpdateTimestampVerifier::verifier(&updater, &STELLAR_CONFIG.trusted_updaters(env));
+    let write_timestamp = now(env);
+
+    let mut updated_feeds = Vec::new(env);
+    let mut updated_values = Vec::new(env);
+    let mut updated_feed_ids = Vec::new(env);
+
+    for (feed_id, value) in values.iter() {
+        let updated = match env.get_value_type(&feed_id) {
+            ValueType::Price => {
+                let price_data = PriceData {
+                    price: U256::from_be_bytes(env, &value),
+                    package_timestamp,
+                    write_timestamp: write_timestamp.as_millis(),
+                };
+
+                let updated =
+                    update_feed(env, &verifier, &feed_id, &price_data, HISTORY_LIMIT.get());
+                if updated {
+                    updated_feeds.push_back(price_data);
+                }
+
+                updated
+            },
+            ValueType::Signed | ValueType::Bytes => {
+                let value_data = ValueData {
+                    value,
+                    package_timestamp,
+                    write_timestamp: write_timestamp.as_millis(),
+                };
+
+                let updated = update_value_feed(env, &verifier, &feed_id, &value_data);
+                if updated {
+                    updated_values.push_back(value_data);
+                }
+
+                updated
+            },
+        };
+
+        if updated {
+            env.save_feed_data_service(&feed_id, service_id);
+            updated_feed_ids.push_back(feed_id);
+        }
+    }
+
+    if !updated_values.is_empty() {
+        env.events().publish_event(&WriteValues {
+            updater: updater.clone(),
+            updated_values,
+        });
+    }
+
+    if !updated_feeds.is_empty() {
+        env.events().publish_event(&WritePrices {
+            updated_feeds,
+            updater,
+        });
+    }
+
+    updated_feed_ids
 }
 
 fn update_feed(
@@ -234,18 +572,12 @@ fn update_feed(
     let mut storage = env.get_data_for_feed_or_default(feed_id);
     let old_price_data = env.get_latest_price_data_for_feed(feed_id);
 
-    if verifier
-        .verify_timestamp(
-            price_data.write_timestamp.into(),
-            old_price_data.as_ref().map(|pd| pd.write_timestamp.into()),
-            STELLAR_CONFIG.min_interval_between_updates_ms.into(),
-            old_price_data
-                .as_ref()
-                .map(|pd| pd.package_timestamp.into()),
-            price_data.package_timestamp.into(),
-        )
-        .is_err()
-    {
+    if !can_update(
+        verifier,
+        price_data.write_timestamp,
+        price_data.package_timestamp,
+        old_price_data.map(|pd| (pd.write_timestamp, pd.package_timestamp)),
+    ) {
         return false;
     }
 
@@ -258,6 +590,45 @@ fn update_feed(
     true
 }
 
+fn update_value_feed(
+    env: &Env,
+    verifier: &UpdateTimestampVerifier,
+    feed_id: &String,
+    value_data: &ValueData,
+) -> bool {
+    let old_value_data = env.get_latest_value_data_for_feed(feed_id);
+
+    if !can_update(
+        verifier,
+        value_data.write_timestamp,
+        value_data.package_timestamp,
+        old_value_data.map(|vd| (vd.write_timestamp, vd.package_timestamp)),
+    ) {
+        return false;
+    }
+
+    env.save_value_feed(feed_id, value_data);
+
+    true
+}
+
+fn can_update(
+    verifier: &UpdateTimestampVerifier,
+    write_timestamp: u64,
+    package_timestamp: u64,
+    old_timestamps: Option<(u64, u64)>,
+) -> bool {
+    verifier
+        .verify_timestamp(
+            write_timestamp.into(),
+            old_timestamps.map(|(write, _)| write.into()),
+            STELLAR_CONFIG.min_interval_between_updates_ms.into(),
+            old_timestamps.map(|(_, package)| package.into()),
+            package_timestamp.into(),
+        )
+        .is_ok()
+}
+
 fn error_from_redstone_error(error: RedStoneError) -> Error {
     Error::from_contract_error(error.code().into())
 }
@@ -1,9 +1,11 @@
 #![cfg(test)]
 
+mod property;
 mod test_contract;
 
 use core::time::Duration;
 
+use common::ValueType;
 use redstone_testing::{
     primary_signers_scenarios::{
         scenario_2_feed_update, scenario_adapter_update_with_almost_future_timestamp,
@@ -20,13 +22,17 @@ use redstone_testing::{
     sample::{sample_btc_eth_3sig, sample_btc_eth_3sig_newer, Sample},
 };
 use soroban_sdk::{
-    testutils::{storage::Persistent, Ledger},
-    Bytes, Env, String, Vec as SorobanVec,
+    testutils::{storage::Persistent, Address as _, Ledger},
+    Address, Bytes, Env, String, Vec as SorobanVec,
 };
 
 use self::test_contract::TestContract;
 use crate::{
-    config::{FEED_TTL_EXTEND_TO, FEED_TTL_SECS, FEED_TTL_THRESHOLD, STELLAR_CONFIG},
+    config::{
+        FEED_TTL_EXTEND_TO, FEED_TTL_SECS, FEED_TTL_THRESHOLD, PRIMARY_DATA_SERVICE_ID,
+        STELLAR_CONFIG,
+    },
+    data_service::DataService,
     RedStoneAdapter, RedStoneAdapterClient, StorageKey, DATA_STALENESS,
 };
 
@@ -175,6 +181,10 @@ fn read_stale_data() {
     scenario.run(contract);
 }
 
+fn primary_data_service(env: &Env) -> String {
+    String::from_str(env, PRIMARY_DATA_SERVICE_ID)
+}
+
 fn write_prices(client: &RedStoneAdapterClient, sample: Sample, expected_ttl: u32) {
     let env = &client.env;
 
@@ -185,6 +195,7 @@ fn write_prices(client: &RedStoneAdapterClient, sample: Sample, expected_ttl: u3
     env.ledger().set_timestamp(sample.timestamp / 1000);
     client.write_prices(
         &client.address,
+        &primary_data_service(env),
         &SorobanVec::from_array(env, [btc.clone()]),
         &payload,
     );
@@ -244,3 +255,296 @@ fn test_feeds_storage_ttl_update_after_threshold() {
         2 * FEED_TTL_EXTEND_TO - FEED_TTL_THRESHOLD - move_ledgers,
     );
 }
+
+#[test]
+fn test_get_and_cache_prices() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(RedStoneAdapter, ());
+    let client = RedStoneAdapterClient::new(&env, &contract_id);
+
+    let sample = sample_btc_eth_3sig();
+    let btc = String::from_str(&env, "BTC");
+    let feed_ids = SorobanVec::from_array(&env, [btc.clone()]);
+    let payload = Bytes::from_slice(&env, &hex::decode(sample.content).unwrap());
+
+    env.ledger().set_timestamp(sample.timestamp / 1000);
+    let (timestamp, prices, cached_feeds) = client.get_and_cache_prices(
+        &client.address,
+        &primary_data_service(&env),
+        &feed_ids,
+        &payload,
+    );
+
+    assert_eq!(cached_feeds, feed_ids);
+    assert_eq!(client.read_prices(&feed_ids), prices);
+    assert_eq!(client.read_timestamp(&btc), timestamp);
+
+    let (_, cached_prices, cached_feeds) = client.get_and_cache_prices(
+        &client.address,
+        &primary_data_service(&env),
+        &feed_ids,
+        &payload,
+    );
+
+    assert!(cached_feeds.is_empty());
+    assert_eq!(cached_prices, prices);
+    assert!(env.events().all().events().is_empty());
+}
+
+#[test]
+fn test_write_bytes_value() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(RedStoneAdapter, ());
+    let client = RedStoneAdapterClient::new(&env, &contract_id);
+    client.init(&Address::generate(&env));
+
+    let sample = sample_btc_eth_3sig();
+    let btc = String::from_str(&env, "BTC");
+    let feed_ids = SorobanVec::from_array(&env, [btc.clone()]);
+    let payload = Bytes::from_slice(&env, &hex::decode(sample.content).unwrap());
+
+    client.set_value_type(&btc, &ValueType::Bytes);
+    assert_eq!(client.read_value_type(&btc), ValueType::Bytes);
+
+    env.ledger().set_timestamp(sample.timestamp / 1000);
+    let (timestamp, prices) = client.get_prices(&primary_data_service(&env), &feed_ids, &payload);
+    client.write_prices(
+        &client.address,
+        &primary_data_service(&env),
+        &feed_ids,
+        &payload,
+    );
+
+    let value_data = client.read_value_data_for_feed(&btc);
+    assert_eq!(value_data.value, prices.get(0).unwrap().to_be_bytes());
+    assert_eq!(value_data.package_timestamp, timestamp);
+
+    assert!(client.try_read_prices(&feed_ids).is_err());
+    assert!(client.try_read_signed_value_for_feed(&btc).is_err());
+
+    env.as_contract(&client.address, || {
+        let ttl = env
+            .storage()
+            .persistent()
+            .get_ttl(&StorageKey::ValueType(btc));
+        assert_eq!(ttl, FEED_TTL_EXTEND_TO);
+    });
+}
+
+#[test]
+fn test_price_getters_reject_value_feeds() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(RedStoneAdapter, ());
+    let client = RedStoneAdapterClient::new(&env, &contract_id);
+    client.init(&Address::generate(&env));
+
+    let sample = sample_btc_eth_3sig();
+    let btc = String::from_str(&env, "BTC");
+    let feed_ids = SorobanVec::from_array(&env, [btc.clone()]);
+    let payload = Bytes::from_slice(&env, &hex::decode(sample.content).unwrap());
+
+    env.ledger().set_timestamp(sample.timestamp / 1000);
+    client.write_prices(
+        &client.address,
+        &primary_data_service(&env),
+        &feed_ids,
+        &payload,
+    );
+    let price_data = client.read_price_data_for_feed(&btc);
+
+    client.set_value_type(&btc, &ValueType::Signed);
+
+    assert!(client.try_read_prices(&feed_ids).is_err());
+    assert!(client.try_read_price_data(&feed_ids).is_err());
+    assert!(client.try_read_price_data_for_feed(&btc).is_err());
+    assert!(client.try_read_price_history(&btc, &1).is_err());
+    assert!(client.try_read_timestamp(&btc).is_err());
+
+    client.set_value_type(&btc, &ValueType::Price);
+
+    assert_eq!(
+        client.read_price_data_for_feed(&btc).price,
+        price_data.price
+    );
+}
+
+#[test]
+fn test_feed_alias() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(RedStoneAdapter, ());
+    let client = RedStoneAdapterClient::new(&env, &contract_id);
+    client.init(&Address::generate(&env));
+
+    let sample = sample_btc_eth_3sig();
+    let btc = String::from_str(&env, "BTC");
+    let btc_usd = String::from_str(&env, "BTC/USD");
+    let payload = Bytes::from_slice(&env, &hex::decode(sample.content).unwrap());
+
+    client.set_feed_alias(&btc_usd, &btc);
+    assert_eq!(client.read_feed_alias(&btc_usd), Some(btc.clone()));
+    assert_eq!(
+        client.canonical_feed_id(&btc_usd),
+        client.canonical_feed_id(&btc)
+    );
+    assert!(client.try_set_feed_alias(&btc, &btc_usd).is_err());
+
+    env.ledger().set_timestamp(sample.timestamp / 1000);
+    client.write_prices(
+        &client.address,
+        &primary_data_service(&env),
+        &SorobanVec::from_array(&env, [btc_usd.clone()]),
+        &payload,
+    );
+
+    assert_eq!(
+        client.read_price_data_for_feed(&btc).price,
+        client.read_price_data_for_feed(&btc_usd).price
+    );
+    assert!(client
+        .try_get_prices(
+            &primary_data_service(&env),
+            &SorobanVec::from_array(&env, [btc, btc_usd]),
+            &payload
+        )
+        .is_err());
+}
+
+#[test]
+fn test_feed_alias_validation() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(RedStoneAdapter, ());
+    let client = RedStoneAdapterClient::new(&env, &contract_id);
+    client.init(&Address::generate(&env));
+
+    let sample = sample_btc_eth_3sig();
+    let btc = String::from_str(&env, "BTC");
+    let eth = String::from_str(&env, "ETH");
+    let btc_usd = String::from_str(&env, "BTC/USD");
+    let payload = Bytes::from_slice(&env, &hex::decode(sample.content).unwrap());
+
+    env.ledger().set_timestamp(sample.timestamp / 1000);
+    client.write_prices(
+        &client.address,
+        &primary_data_service(&env),
+        &SorobanVec::from_array(&env, [eth.clone()]),
+        &payload,
+    );
+
+    assert!(client.try_set_feed_alias(&eth, &btc).is_err());
+    assert!(client
+        .try_set_feed_alias(&String::from_bytes(&env, b"\0BTC/USD"), &btc)
+        .is_err());
+
+    client.set_feed_alias(&String::from_bytes(&env, b"BTC/USD\0"), &btc);
+    assert_eq!(client.read_feed_alias(&btc_usd), Some(btc.clone()));
+    assert_eq!(
+        client.read_value_type(&String::from_bytes(&env, b"BTC/USD\0\0")),
+        ValueType::Price
+    );
+    assert!(client.try_set_feed_alias(&btc, &eth).is_err());
+    assert!(client.try_set_feed_alias(&eth, &btc_usd).is_err());
+
+    env.as_contract(&client.address, || {
+        let ttl = env
+            .storage()
+            .persistent()
+            .get_ttl(&StorageKey::Alias(btc_usd.clone()));
+        assert_eq!(ttl, FEED_TTL_EXTEND_TO);
+    });
+
+    client.remove_feed_alias(&btc_usd);
+    assert_eq!(client.read_feed_alias(&btc_usd), None);
+    client.set_feed_alias(&btc, &String::from_str(&env, "XBT"));
+}
+
+#[test]
+fn test_data_service_storage_ttl() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(RedStoneAdapter, ());
+    let client = RedStoneAdapterClient::new(&env, &contract_id);
+    client.init(&Address::generate(&env));
+
+    let custom = String::from_str(&env, "custom-lst-service");
+    let data_service = DataService {
+        allowed_feeds: SorobanVec::from_array(&env, [String::from_str(&env, "ETH")]),
+        ..DataService::primary(&env)
+    };
+    let key = StorageKey::DataService(custom.clone());
+    let ttl = || env.as_contract(&client.address, || env.storage().persistent().get_ttl(&key));
+
+    client.set_data_service(&custom, &data_service);
+    assert_eq!(ttl(), FEED_TTL_EXTEND_TO);
+
+    let move_ledgers = FEED_TTL_EXTEND_TO - FEED_TTL_THRESHOLD + 1;
+    env.ledger().set_sequence_number(move_ledgers);
+
+    assert_eq!(client.read_data_service(&custom), data_service);
+    assert_eq!(ttl(), FEED_TTL_EXTEND_TO);
+}
+
+#[test]
+fn test_custom_data_service() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(RedStoneAdapter, ());
+    let client = RedStoneAdapterClient::new(&env, &contract_id);
+    client.init(&Address::generate(&env));
+
+    let sample = sample_btc_eth_3sig();
+    let btc = String::from_str(&env, "BTC");
+    let eth = String::from_str(&env, "ETH");
+    let custom = String::from_str(&env, "custom-lst-service");
+    let payload = Bytes::from_slice(&env, &hex::decode(sample.content).unwrap());
+
+    let data_service = DataService {
+        allowed_feeds: SorobanVec::from_array(&env, [eth.clone()]),
+        ..DataService::primary(&env)
+    };
+    client.set_data_service(&custom, &data_service);
+    assert_eq!(client.read_data_service(&custom), data_service);
+    assert!(client
+        .try_set_data_service(&primary_data_service(&env), &data_service)
+        .is_err());
+    assert!(client
+        .try_set_data_service(&custom, &DataService::primary(&env))
+        .is_err());
+    assert!(client
+        .try_set_data_service(
+            &custom,
+            &DataService {
+                signer_count_threshold: 0,
+                ..data_service.clone()
+            }
+        )
+        .is_err());
+
+    env.ledger().set_timestamp(sample.timestamp / 1000);
+    assert!(client
+        .try_write_prices(
+            &client.address,
+            &custom,
+            &SorobanVec::from_array(&env, [btc.clone()]),
+            &payload
+        )
+        .is_err());
+
+    client.write_prices(
+        &client.address,
+        &custom,
+        &SorobanVec::from_array(&env, [eth.clone()]),
+        &payload,
+    );
+    assert_eq!(client.read_feed_data_service(&eth), custom);
+    assert!(client.try_read_feed_data_service(&btc).is_err());
+
+    client.remove_data_service(&custom);
+    assert!(client
+        .try_get_prices(&custom, &SorobanVec::from_array(&env, [eth]), &payload)
+        .is_err());
+}
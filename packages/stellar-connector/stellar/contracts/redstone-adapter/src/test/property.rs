@@ -0,0 +1,194 @@
+use alloc::vec::Vec;
+
+use common::PriceData;
+use proptest::{collection::vec, prelude::*, sample::select};
+use redstone_testing::sample::{sample_btc_eth_3sig, sample_btc_eth_3sig_newer, Sample};
+use soroban_sdk::{
+    testutils::{storage::Persistent, Address as _, Ledger},
+    Address, Bytes, Env, String, Vec as SorobanVec,
+};
+
+use crate::{
+    config::{FEED_TTL_EXTEND_TO, FEED_TTL_THRESHOLD, PRIMARY_DATA_SERVICE_ID, STELLAR_CONFIG},
+    env_extensions::EnvExt,
+    RedStoneAdapter, RedStoneAdapterClient, StorageKey, HISTORY_LIMIT,
+};
+
+const FEEDS: [&str; 2] = ["BTC", "ETH"];
+const FEED_SUBSETS: [&[&str]; 3] = [&["BTC"], &["ETH"], &["BTC", "ETH"]];
+
+/// Ledger time offsets around the package timestamp, wide enough to fall out of
+/// the accepted delay/ahead window.
+const MAX_TIME_OFFSET_SECS: i64 = 5 * 60;
+const MAX_LEDGERS_BETWEEN_WRITES: u32 = 1_000;
+
+#[derive(Clone, Debug)]
+enum Updater {
+    Trusted,
+    Untrusted(usize),
+}
+
+#[derive(Clone, Debug)]
+struct WriteOp {
+    newer_sample: bool,
+    feeds: &'static [&'static str],
+    updater: Updater,
+    time_offset_secs: i64,
+    ledgers_passed: u32,
+}
+
+fn updater_strategy() -> impl Strategy<Value = Updater> {
+    prop_oneof![
+        Just(Updater::Trusted),
+        (0..3usize).prop_map(Updater::Untrusted)
+    ]
+}
+
+fn write_op_strategy() -> impl Strategy<Value = WriteOp> {
+    (
+        any::<bool>(),
+        select(FEED_SUBSETS.to_vec()),
+        updater_strategy(),
+        -MAX_TIME_OFFSET_SECS..=MAX_TIME_OFFSET_SECS,
+        0..=MAX_LEDGERS_BETWEEN_WRITES,
+    )
+        .prop_map(
+            |(newer_sample, feeds, updater, time_offset_secs, ledgers_passed)| WriteOp {
+                newer_sample,
+                feeds,
+                updater,
+                time_offset_secs,
+                ledgers_passed,
+            },
+        )
+}
+
+struct FeedSnapshot {
+    latest: PriceData,
+    history: SorobanVec<PriceData>,
+    ttl: u32,
+}
+
+struct Harness {
+    client: RedStoneAdapterClient<'static>,
+    untrusted: [Address; 3],
+}
+
+impl Harness {
+    fn new() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(RedStoneAdapter, ());
+        let client = RedStoneAdapterClient::new(&env, &contract_id);
+        let untrusted = [(); 3].map(|_| Address::generate(&env));
+
+        Self { client, untrusted }
+    }
+
+    fn apply(&self, op: &WriteOp) -> Result<(), TestCaseError> {
+        let env = &self.client.env;
+        let sample: Sample = if op.newer_sample {
+            sample_btc_eth_3sig_newer()
+        } else {
+            sample_btc_eth_3sig()
+        };
+
+        let ledger_time = (sample.timestamp / 1000).saturating_add_signed(op.time_offset_secs);
+        env.ledger().set_timestamp(ledger_time);
+        env.ledger()
+            .set_sequence_number(env.ledger().sequence() + op.ledgers_passed);
+
+        let updater = match op.updater {
+            Updater::Trusted => STELLAR_CONFIG.trusted_updaters(env)[0].clone(),
+            Updater::Untrusted(i) => self.untrusted[i].clone(),
+        };
+        let feed_ids: Vec<String> = op
+            .feeds
+            .iter()
+            .map(|feed| String::from_str(env, feed))
+            .collect();
+        let payload = Bytes::from_slice(env, &hex::decode(sample.content).unwrap());
+
+        let before = self.snapshot();
+        let result = self.client.try_write_prices(
+            &updater,
+            &String::from_str(env, PRIMARY_DATA_SERVICE_ID),
+            &SorobanVec::from_slice(env, &feed_ids),
+            &payload,
+        );
+        let after = self.snapshot();
+
+        for ((feed, before), after) in FEEDS.iter().zip(before).zip(after) {
+            let Some(after) = after else {
+                prop_assert!(before.is_none());
+                continue;
+            };
+
+            prop_assert!(after.history.len() <= HISTORY_LIMIT.get());
+            prop_assert_eq!(
+                after.history.last().unwrap().package_timestamp,
+                after.latest.package_timestamp
+            );
+            prop_assert!(after.latest.write_timestamp <= ledger_time * 1000);
+
+            let ttl_before = before.as_ref().map(|before| before.ttl);
+            let updated = match before {
+                Some(before) => {
+                    prop_assert!(after.latest.package_timestamp >= before.latest.package_timestamp);
+
+                    after.history != before.history
+                },
+                None => true,
+            };
+
+            if updated {
+                prop_assert!(result.is_ok());
+                prop_assert!(op.feeds.contains(feed));
+                match ttl_before {
+                    Some(ttl_before) if ttl_before > FEED_TTL_THRESHOLD => {
+                        prop_assert_eq!(after.ttl, ttl_before)
+                    },
+                    _ => prop_assert_eq!(after.ttl, FEED_TTL_EXTEND_TO),
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn snapshot(&self) -> [Option<FeedSnapshot>; FEEDS.len()] {
+        let env = &self.client.env;
+
+        env.as_contract(&self.client.address, || {
+            FEEDS.map(|feed| {
+                let feed_id = String::from_str(env, feed);
+                let latest = env.get_latest_price_data_for_feed(&feed_id)?;
+                let history = env.get_data_for_feed(&feed_id).ok()?.get_all();
+                let ttl = env
+                    .storage()
+                    .persistent()
+                    .get_ttl(&StorageKey::Feed(feed_id));
+
+                Some(FeedSnapshot {
+                    latest,
+                    history,
+                    ttl,
+                })
+            })
+        })
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn write_prices_preserves_storage_invariants(ops in vec(write_op_strategy(), 1..24)) {
+        let harness = Harness::new();
+
+        for op in ops.iter() {
+            harness.apply(op)?;
+        }
+    }
+}
@@ -4,7 +4,10 @@ use core::time::Duration;
 use redstone_testing::{env::PriceAdapterRunEnv, redstone::Value, signer::ContractUpdateSigner};
 use soroban_sdk::{testutils::Ledger, Bytes, Env, String, Vec as SorobanVec};
 
-use crate::{config::STELLAR_CONFIG, RedStoneAdapter, RedStoneAdapterClient};
+use crate::{
+    config::{PRIMARY_DATA_SERVICE_ID, STELLAR_CONFIG},
+    RedStoneAdapter, RedStoneAdapterClient,
+};
 
 pub struct TestContract(RedStoneAdapterClient<'static>);
 
@@ -85,7 +88,10 @@ impl PriceAdapterRunEnv for TestContract {
             ContractUpdateSigner::Untrusted => &self.0.address,
         };
 
-        self.0.write_prices(address, &feed_ids, &payload);
+        let service_id = String::from_str(&self.0.env, PRIMARY_DATA_SERVICE_ID);
+
+        self.0
+            .write_prices(address, &service_id, &feed_ids, &payload);
     }
 
     fn process_payload_get(
@@ -2,6 +2,7 @@ use redstone::{FeedId, TimestampMillis};
 use soroban_sdk::{Env, String};
 
 const MS_IN_SEC: u64 = 1_000;
+const FEED_ID_LEN: usize = 32;
 
 /// Converts a `FeedId` (32-byte array) to a Soroban `String` by stripping
 /// leading and trailing zero bytes.
@@ -9,12 +10,10 @@ const MS_IN_SEC: u64 = 1_000;
 /// **Normalization caveat:** This function strips *all* leading and trailing
 /// zero bytes, so two distinct `FeedId` values can produce the same string. For
 /// example, `[0x00, 'B', 'T', 'C', 0x00, ...]` and `[0x00, 0x00, 'B', 'T', 'C',
-/// 0x00, ...]` both normalize to `"BTC"`. In practice this is not a problem
-/// because feed IDs arriving from the RedStone payload are always left-aligned
-/// with trailing zeros and will never carry leading zeros. However, any future
-/// code that deduplicates or indexes feed IDs must call this function *before*
-/// comparing — comparing raw `FeedId` bytes would incorrectly treat the two
-/// examples above as different feeds.
+/// 0x00, ...]` both normalize to `"BTC"`. Feed IDs arriving from the RedStone
+/// payload are always left-aligned with trailing zeros, so the adapter uses
+/// [`feed_to_canonical_string`], which rejects the ambiguous ids instead of
+/// mapping them onto the same string.
 pub fn feed_to_string(env: &Env, feed: FeedId) -> String {
     let feed_bytes = feed.to_array();
 
@@ -28,6 +27,46 @@ pub fn feed_to_string(env: &Env, feed: FeedId) -> String {
     String::from_bytes(env, trimmed)
 }
 
+/// Converts a `FeedId` to a Soroban `String` that converts back to the same
+/// `FeedId` with [`string_to_feed`].
+///
+/// Returns `None` for ids that have no such representation, i.e. ids that are
+/// all zeros or start with a zero byte.
+pub fn feed_to_canonical_string(env: &Env, feed: FeedId) -> Option<String> {
+    let feed_bytes = feed.to_array();
+
+    if feed_bytes[0] == 0 {
+        return None;
+    }
+
+    Some(feed_to_string(env, FeedId::from(feed_bytes)))
+}
+
+/// Encodes a Soroban `String` as a `FeedId` by left-aligning its bytes and
+/// padding them with trailing zeros.
+///
+/// Returns `None` if the string is empty or longer than 32 bytes.
+pub fn string_to_feed(feed: &String) -> Option<FeedId> {
+    let len = feed.len() as usize;
+
+    if len == 0 || len > FEED_ID_LEN {
+        return None;
+    }
+
+    let mut feed_bytes = [0u8; FEED_ID_LEN];
+    feed.copy_into_slice(&mut feed_bytes[..len]);
+
+    Some(FeedId::from(feed_bytes))
+}
+
+/// Brings a feed string to the form in which it is stored, stripping the
+/// trailing zero bytes.
+///
+/// Returns `None` if the string can't be encoded as a `FeedId` unambiguously.
+pub fn normalize_feed_string(env: &Env, feed: &String) -> Option<String> {
+    feed_to_canonical_string(env, string_to_feed(feed)?)
+}
+
 pub fn now(env: &Env) -> TimestampMillis {
     TimestampMillis::from_millis(env.ledger().timestamp() * MS_IN_SEC)
 }
@@ -59,3 +98,45 @@ fn test_feed_to_string_simple() {
         convert_non_btc_feed_id_to_string
     );
 }
+
+#[test]
+fn test_feed_canonical_string_round_trip() {
+    let env = Env::default();
+
+    let eth = String::from_str(&env, "ETH");
+    let eth_feed_id = string_to_feed(&eth).unwrap();
+    let mut eth_feed_id_array = [0u8; FEED_ID_LEN];
+    eth_feed_id_array[..3].copy_from_slice(b"ETH");
+
+    assert_eq!(eth_feed_id.to_array(), eth_feed_id_array);
+    assert_eq!(
+        feed_to_canonical_string(&env, FeedId::from(eth_feed_id_array)),
+        Some(eth.clone())
+    );
+    assert_eq!(
+        normalize_feed_string(&env, &String::from_bytes(&env, b"ETH\0\0")),
+        Some(eth)
+    );
+}
+
+#[test]
+fn test_feed_canonical_string_rejects_ambiguous_ids() {
+    let env = Env::default();
+
+    let mut leading_zero_feed_id_array = [0u8; FEED_ID_LEN];
+    leading_zero_feed_id_array[1..4].copy_from_slice(b"BTC");
+
+    assert_eq!(
+        feed_to_canonical_string(&env, FeedId::from(leading_zero_feed_id_array)),
+        None
+    );
+    assert_eq!(
+        normalize_feed_string(&env, &String::from_bytes(&env, b"\0BTC")),
+        None
+    );
+    assert_eq!(
+        normalize_feed_string(&env, &String::from_str(&env, "")),
+        None
+    );
+    assert!(string_to_feed(&String::from_bytes(&env, &[b'A'; 33])).is_none());
+}
@@ -1,13 +1,7 @@
-use alloy_primitives::hex;
-use redstone_stylus::config::{Config, SignerAddressBs, REDSTONE_PRIMARY_PROD_ALLOWED_SIGNERS};
-
-pub const STYLUS_CONFIG: Config = Config {
-    signer_count_threshold: 3,
-    signers: REDSTONE_PRIMARY_PROD_ALLOWED_SIGNERS,
-    trusted_updaters: TRUSTED_UPDATERS,
-    max_timestamp_ahead_ms: 3 * 60 * 1_000,
-    max_timestamp_delay_ms: 3 * 60 * 1_000,
-    min_interval_between_updates_ms: 40_000,
-};
-
-const TRUSTED_UPDATERS: [SignerAddressBs; 1] = [hex!("0b8c05816821CF80078e198cAea7bd6a6eD4a0e7")];
+pub const MAX_TIMESTAMP_AHEAD_MS: u64 = 3 * 60 * 1_000;
+pub const MAX_TIMESTAMP_DELAY_MS: u64 = 3 * 60 * 1_000;
+pub const MIN_INTERVAL_BETWEEN_UPDATES_MS: u64 = 40_000;
+/// Max age of the data read by `latest_round_data` of the feeds without the `max_data_age_ms` set
+pub const DEFAULT_MAX_DATA_AGE_MS: u64 = 24 * 60 * 60 * 1_000;
+/// The adapter has no rounds, so the latest one is always reported with the same id
+pub const LATEST_ROUND_ID: u64 = 1;
@@ -1,4 +1,5 @@
 use alloy_primitives::B256;
+use openzeppelin_stylus::access::ownable::{self, OwnableInvalidOwner, OwnableUnauthorizedAccount};
 use redstone::network::error::Error;
 use stylus_sdk::{alloy_sol_types::sol, prelude::SolidityError};
 
@@ -6,13 +7,23 @@ sol! {
     error SdkError(uint16 error_code);
     error DataFeedNotFound(bytes32 feed);
     error NotEnoughResults();
+    error UpdateNotRequired(bytes32 feed);
+    error StaleData(bytes32 feed, uint256 age);
+    error UnsafeUintToIntConversion(uint256 value);
+    error InvalidCalldata();
 }
 
-#[derive(SolidityError)]
+#[derive(SolidityError, Clone)]
 pub enum RedStoneError {
     SdkError(SdkError),
     DataFeedNotFound(DataFeedNotFound),
     NotEnoughResults(NotEnoughResults),
+    UnauthorizedAccount(OwnableUnauthorizedAccount),
+    InvalidOwner(OwnableInvalidOwner),
+    UpdateNotRequired(UpdateNotRequired),
+    StaleData(StaleData),
+    UnsafeUintToIntConversion(UnsafeUintToIntConversion),
+    InvalidCalldata(InvalidCalldata),
 }
 
 impl From<Error> for RedStoneError {
@@ -28,3 +39,12 @@ impl From<B256> for RedStoneError {
         RedStoneError::DataFeedNotFound(DataFeedNotFound { feed })
     }
 }
+
+impl From<ownable::Error> for RedStoneError {
+    fn from(ownable_error: ownable::Error) -> Self {
+        match ownable_error {
+            ownable::Error::UnauthorizedAccount(error) => RedStoneError::UnauthorizedAccount(error),
+            ownable::Error::InvalidOwner(error) => RedStoneError::InvalidOwner(error),
+        }
+    }
+}
@@ -0,0 +1,8 @@
+use stylus_sdk::alloy_sol_types::sol;
+
+sol! {
+    /// Emitted for every feed written, `updatedAt` being the timestamp of the data package in ms
+    event ValueUpdate(uint256 value, bytes32 dataFeedId, uint256 updatedAt);
+    /// Emitted for a feed skipped by a partial update, with the ABI-encoded error it failed with
+    event UpdateSkipped(bytes32 dataFeedId, bytes reason);
+}
@@ -0,0 +1,103 @@
+use alloy_primitives::{B256, U16, U256, U64};
+use stylus_sdk::prelude::*;
+
+use crate::error::{RedStoneError, StaleData};
+
+const BPS_DENOMINATOR: u64 = 10_000;
+
+sol_storage! {
+    /// Per-feed write conditions set by the owner, checked only for the untrusted updaters.
+    /// The zeroed settings keep the former behavior.
+    pub struct FeedSettings {
+        /// Time between the package timestamps after which an update is always accepted, 0 if not checked.
+        uint64 heartbeat_ms;
+        /// Price change in basis points for which an update is accepted before the heartbeat, 0 if not checked.
+        uint16 deviation_threshold_bps;
+        /// Max age of the package written, 0 if only the `MAX_TIMESTAMP_DELAY_MS` is checked.
+        uint64 max_data_age_ms;
+    }
+}
+
+impl FeedSettings {
+    pub fn set(&mut self, heartbeat_ms: u64, deviation_threshold_bps: u16, max_data_age_ms: u64) {
+        self.heartbeat_ms.set(U64::from(heartbeat_ms));
+        self.deviation_threshold_bps
+            .set(U16::from(deviation_threshold_bps));
+        self.max_data_age_ms.set(U64::from(max_data_age_ms));
+    }
+
+    /// Returns (heartbeatMs, deviationThresholdBps, maxDataAgeMs)
+    pub fn get(&self) -> (u64, u16, u64) {
+        (
+            self.heartbeat_ms.get().to::<u64>(),
+            self.deviation_threshold_bps.get().to::<u16>(),
+            self.max_data_age_ms.get().to::<u64>(),
+        )
+    }
+
+    pub fn max_data_age_ms(&self) -> u64 {
+        self.max_data_age_ms.get().to::<u64>()
+    }
+
+    /// The `current_timestamp` is the package timestamp of the stored value, 0 if not written yet.
+    pub fn is_update_required(
+        &self,
+        current_value: U256,
+        current_timestamp: u64,
+        value: U256,
+        timestamp: u64,
+    ) -> bool {
+        let (heartbeat_ms, deviation_threshold_bps, _) = self.get();
+
+        if current_timestamp == 0 || (heartbeat_ms == 0 && deviation_threshold_bps == 0) {
+            return true;
+        }
+
+        if heartbeat_ms != 0 && timestamp.saturating_sub(current_timestamp) >= heartbeat_ms {
+            return true;
+        }
+
+        if deviation_threshold_bps == 0 {
+            return false;
+        }
+
+        current_value.is_zero()
+            || current_value
+                .abs_diff(value)
+                .saturating_mul(U256::from(BPS_DENOMINATOR))
+                >= current_value.saturating_mul(U256::from(deviation_threshold_bps))
+    }
+
+    pub fn verify_data_age(
+        &self,
+        feed: B256,
+        timestamp: u64,
+        block_timestamp_millis: u64,
+    ) -> Result<(), RedStoneError> {
+        match self.max_data_age_ms() {
+            0 => Ok(()),
+            max_data_age_ms => {
+                verify_data_age(feed, timestamp, block_timestamp_millis, max_data_age_ms)
+            }
+        }
+    }
+}
+
+/// Fails with `StaleData` when the package `timestamp` is older than `max_data_age_ms`.
+pub fn verify_data_age(
+    feed: B256,
+    timestamp: u64,
+    block_timestamp_millis: u64,
+    max_data_age_ms: u64,
+) -> Result<(), RedStoneError> {
+    let age = block_timestamp_millis.saturating_sub(timestamp);
+
+    if age > max_data_age_ms {
+        return Err(RedStoneError::StaleData(StaleData {
+            feed,
+            age: U256::from(age),
+        }));
+    }
+
+    Ok(())
+}
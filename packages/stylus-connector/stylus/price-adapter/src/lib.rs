@@ -4,20 +4,39 @@ extern crate alloc;
 use alloc::vec::Vec;
 
 use crate::{
-    config::STYLUS_CONFIG,
-    error::{NotEnoughResults, RedStoneError},
+    config::{
+        DEFAULT_MAX_DATA_AGE_MS, LATEST_ROUND_ID, MAX_TIMESTAMP_AHEAD_MS, MAX_TIMESTAMP_DELAY_MS,
+        MIN_INTERVAL_BETWEEN_UPDATES_MS,
+    },
+    error::{NotEnoughResults, RedStoneError, UnsafeUintToIntConversion, UpdateNotRequired},
+    events::{UpdateSkipped, ValueUpdate},
+    feed_settings::{verify_data_age, FeedSettings},
+    multi_feed::decode_update_data_feeds_values_partial,
+    payload_feeds::count_feed_packages,
 };
-use alloy_primitives::{B256, U256};
+use alloy_primitives::{aliases::U80, Address, B256, I256, U256, U8};
+use openzeppelin_stylus::access::ownable_two_step::{IOwnable2Step, Ownable2Step};
 use redstone::{contract::verification::UpdateTimestampVerifier, TimestampMillis, Value};
-use redstone_stylus::processor::Processor;
+use redstone_stylus::{
+    config::{Config, SignerAddressBs},
+    processor::Processor,
+};
 use stylus_sdk::{
     abi::Bytes,
     prelude::*,
-    storage::{StorageGuard, StorageMap},
+    storage::{StorageAddress, StorageGuard, StorageMap, StorageVec},
+    stylus_core::log,
+    ArbResult,
 };
 
 mod config;
 mod error;
+pub mod events;
+mod feed_settings;
+mod multi_feed;
+mod payload_feeds;
+#[cfg(test)]
+mod tests;
 
 sol_storage! {
     pub struct PriceData {
@@ -27,14 +46,129 @@ sol_storage! {
     }
 }
 
+sol_storage! {
+    pub struct AdapterConfig {
+      uint8 signer_count_threshold;
+      address[] signers;
+      address[] trusted_updaters;
+    }
+}
+
+impl PriceData {
+    /// The block timestamp of the write in seconds, as in the EVM `MultiFeedAdapter`
+    fn block_timestamp(&self) -> U256 {
+        self.write_timestamp.get() / U256::from(1000)
+    }
+}
+
+/// (roundId, answer, startedAt, updatedAt, answeredInRound)
+type RoundData = (U80, I256, U256, U256, U80);
+
 #[entrypoint]
 #[storage]
 struct PriceAdapter {
     prices: StorageMap<B256, PriceData>,
+    ownable: Ownable2Step,
+    config: AdapterConfig,
+    feed_settings: StorageMap<B256, FeedSettings>,
 }
 
 #[public]
 impl PriceAdapter {
+    #[constructor]
+    pub fn constructor(
+        &mut self,
+        owner: Address,
+        signers: Vec<Address>,
+        signer_count_threshold: u8,
+        trusted_updaters: Vec<Address>,
+    ) -> Result<(), RedStoneError> {
+        self.ownable.constructor(owner)?;
+        self.store_signers(signers, signer_count_threshold)?;
+        store_addresses(&mut self.config.trusted_updaters, trusted_updaters);
+
+        Ok(())
+    }
+
+    pub fn owner(&self) -> Address {
+        self.ownable.owner()
+    }
+
+    pub fn pending_owner(&self) -> Address {
+        self.ownable.pending_owner()
+    }
+
+    /// Starts the ownership transfer, completed when the `new_owner` calls `accept_ownership`
+    pub fn transfer_ownership(&mut self, new_owner: Address) -> Result<(), RedStoneError> {
+        Ok(self.ownable.transfer_ownership(new_owner)?)
+    }
+
+    pub fn accept_ownership(&mut self) -> Result<(), RedStoneError> {
+        Ok(self.ownable.accept_ownership()?)
+    }
+
+    pub fn set_signers(
+        &mut self,
+        signers: Vec<Address>,
+        signer_count_threshold: u8,
+    ) -> Result<(), RedStoneError> {
+        self.ownable.only_owner()?;
+
+        self.store_signers(signers, signer_count_threshold)
+    }
+
+    pub fn set_trusted_updaters(
+        &mut self,
+        trusted_updaters: Vec<Address>,
+    ) -> Result<(), RedStoneError> {
+        self.ownable.only_owner()?;
+        store_addresses(&mut self.config.trusted_updaters, trusted_updaters);
+
+        Ok(())
+    }
+
+    /// Sets the conditions of the writes of the `feed` by the untrusted updaters, 0 disables each of them
+    pub fn set_feed_settings(
+        &mut self,
+        feed: B256,
+        heartbeat_ms: u64,
+        deviation_threshold_bps: u16,
+        max_data_age_ms: u64,
+    ) -> Result<(), RedStoneError> {
+        self.ownable.only_owner()?;
+        self.feed_settings
+            .setter(feed)
+            .set(heartbeat_ms, deviation_threshold_bps, max_data_age_ms);
+
+        Ok(())
+    }
+
+    /// Returns (heartbeatMs, deviationThresholdBps, maxDataAgeMs)
+    pub fn get_feed_settings(&self, feed: B256) -> (u64, u16, u64) {
+        self.feed_settings.get(feed).get()
+    }
+
+    /// Tells whether the heartbeat or the deviation threshold allows an untrusted updater
+    /// to write the `value` with the package `timestamp` in ms
+    pub fn is_update_required(&self, feed: B256, value: U256, timestamp: U256) -> bool {
+        let data = self.prices.get(feed);
+
+        self.feed_settings.get(feed).is_update_required(
+            data.value.get(),
+            data.package_timestamp.get().to::<u64>(),
+            value,
+            timestamp.saturating_to::<u64>(),
+        )
+    }
+
+    pub fn get_signers(&self) -> Vec<Address> {
+        load_addresses(&self.config.signers)
+    }
+
+    pub fn get_trusted_updaters(&self) -> Vec<Address> {
+        load_addresses(&self.config.trusted_updaters)
+    }
+
     pub fn get_prices(
         &mut self,
         feeds: Vec<B256>,
@@ -42,14 +176,10 @@ impl PriceAdapter {
     ) -> Result<Vec<U256>, RedStoneError> {
         let block_timestamp = self.vm().block_timestamp();
         let block_timestamp_millis = block_timestamp * 1000;
+        let config = self.load_config();
 
-        let processed_payload = Processor::process_payload(
-            self,
-            STYLUS_CONFIG,
-            &feeds,
-            payload,
-            block_timestamp_millis,
-        )?;
+        let processed_payload =
+            Processor::process_payload(self, &config, &feeds, payload, block_timestamp_millis)?;
         if processed_payload.values.len() != feeds.len() {
             return Err(RedStoneError::NotEnoughResults(NotEnoughResults {}));
         }
@@ -62,39 +192,22 @@ impl PriceAdapter {
     }
 
     pub fn write_prices(&mut self, feeds: Vec<B256>, payload: Bytes) -> Result<(), RedStoneError> {
-        let block_timestamp = self.vm().block_timestamp();
-        let block_timestamp_millis = block_timestamp * 1000;
-
-        let processed_payload = Processor::process_payload(
-            self,
-            STYLUS_CONFIG,
-            &feeds,
-            payload,
-            block_timestamp_millis,
-        )?;
-
-        if processed_payload.values.len() != feeds.len() {
-            return Err(RedStoneError::NotEnoughResults(NotEnoughResults {}));
-        }
-
-        let verifier = UpdateTimestampVerifier::verifier(
-            &self.vm().msg_sender().into_array(),
-            STYLUS_CONFIG.trusted_updaters(),
-        );
-
-        for (new_price, feed) in processed_payload.values.into_iter().zip(feeds) {
-            self.write_new_price(
-                feed,
-                new_price,
-                processed_payload.timestamp,
-                block_timestamp,
-                &verifier,
-            )?;
-        }
+        self.write_feeds(feeds, payload, false)?;
 
         Ok(())
     }
 
+    /// Writes the feeds passing the timestamp verification and skips the others,
+    /// emitting `UpdateSkipped` for them.
+    /// Returns the feeds written and reverts only if none of them could be written
+    pub fn write_prices_partial(
+        &mut self,
+        feeds: Vec<B256>,
+        payload: Bytes,
+    ) -> Result<Vec<B256>, RedStoneError> {
+        self.write_feeds(feeds, payload, true)
+    }
+
     pub fn read_price(&self, feed: B256) -> U256 {
         let feed_price = self.prices.get(feed);
 
@@ -102,7 +215,7 @@ impl PriceAdapter {
     }
 
     pub fn get_unique_signers_threshold(&self) -> u8 {
-        STYLUS_CONFIG.signer_count_threshold
+        self.config.signer_count_threshold.get().to::<u8>()
     }
 
     /// Gets the last update details for a specific data feed
@@ -112,7 +225,7 @@ impl PriceAdapter {
 
         Ok((
             data.package_timestamp.get(),
-            data.write_timestamp.get(),
+            data.block_timestamp(),
             data.value.get(),
         ))
     }
@@ -124,7 +237,7 @@ impl PriceAdapter {
 
         (
             data.package_timestamp.get(),
-            data.write_timestamp.get(),
+            data.block_timestamp(),
             data.value.get(),
         )
     }
@@ -157,6 +270,40 @@ impl PriceAdapter {
         Ok(data.value.get())
     }
 
+    /// Gets the value for a single data feed, failing with `StaleData`
+    /// when its package is older than `max_age_ms`
+    pub fn get_value_for_data_feed_with_max_age(
+        &self,
+        feed: B256,
+        max_age_ms: U256,
+    ) -> Result<U256, RedStoneError> {
+        let data = self.get_fresh_feed_data(feed, max_age_ms.saturating_to::<u64>())?;
+
+        Ok(data.value.get())
+    }
+
+    /// Gets the Chainlink `latestRoundData` of a data feed, with the block timestamp
+    /// of the latest update in seconds as both `startedAt` and `updatedAt`,
+    /// converted from the write timestamp stored in ms.
+    /// Fails with `StaleData` when the package is older than the `maxDataAgeMs` of the feed,
+    /// or `DEFAULT_MAX_DATA_AGE_MS` when it's not set
+    pub fn latest_round_data(&self, feed: B256) -> Result<RoundData, RedStoneError> {
+        let max_data_age_ms = match self.feed_settings.get(feed).max_data_age_ms() {
+            0 => DEFAULT_MAX_DATA_AGE_MS,
+            max_data_age_ms => max_data_age_ms,
+        };
+        let data = self.get_fresh_feed_data(feed, max_data_age_ms)?;
+
+        let value = data.value.get();
+        let answer = I256::try_from(value).map_err(|_| {
+            RedStoneError::UnsafeUintToIntConversion(UnsafeUintToIntConversion { value })
+        })?;
+        let updated_at = data.block_timestamp();
+        let round_id = U80::from(LATEST_ROUND_ID);
+
+        Ok((round_id, answer, updated_at, updated_at, round_id))
+    }
+
     /// Gets the data timestamp from the latest update for a specific data feed
     pub fn get_data_timestamp_from_latest_update(&self, feed: B256) -> Result<U256, RedStoneError> {
         let data = self.get_feed_data(feed)?;
@@ -171,11 +318,53 @@ impl PriceAdapter {
     ) -> Result<U256, RedStoneError> {
         let data = self.get_feed_data(feed)?;
 
-        Ok(data.write_timestamp.get())
+        Ok(data.block_timestamp())
+    }
+
+    /// Handles `updateDataFeedsValuesPartial(bytes32[])` of the EVM `MultiFeedAdapter`,
+    /// with the RedStone payload appended to the calldata instead of passed as an argument.
+    /// Any other calldata fails with `InvalidCalldata`
+    #[fallback]
+    pub fn fallback(&mut self, calldata: &[u8]) -> ArbResult {
+        let (feeds, payload) = decode_update_data_feeds_values_partial(calldata)?;
+        self.write_prices_partial(feeds, payload)?;
+
+        Ok(Vec::new())
     }
 }
 
 impl PriceAdapter {
+    fn load_config(&self) -> Config {
+        Config {
+            signer_count_threshold: self.config.signer_count_threshold.get().to::<u8>(),
+            signers: load_signer_addresses(&self.config.signers),
+            trusted_updaters: load_signer_addresses(&self.config.trusted_updaters),
+            max_timestamp_delay_ms: MAX_TIMESTAMP_DELAY_MS,
+            max_timestamp_ahead_ms: MAX_TIMESTAMP_AHEAD_MS,
+            min_interval_between_updates_ms: MIN_INTERVAL_BETWEEN_UPDATES_MS,
+        }
+    }
+
+    fn store_signers(
+        &mut self,
+        signers: Vec<Address>,
+        signer_count_threshold: u8,
+    ) -> Result<(), RedStoneError> {
+        let config = Config {
+            signer_count_threshold,
+            signers: signers.iter().map(|signer| signer.into_array()).collect(),
+            ..self.load_config()
+        };
+        config.verify()?;
+
+        self.config
+            .signer_count_threshold
+            .set(U8::from(signer_count_threshold));
+        store_addresses(&mut self.config.signers, signers);
+
+        Ok(())
+    }
+
     fn get_feed_data(&self, feed: B256) -> Result<StorageGuard<'_, PriceData>, RedStoneError> {
         let data = self.prices.get(feed);
 
@@ -189,6 +378,172 @@ impl PriceAdapter {
         Ok(data)
     }
 
+    fn get_fresh_feed_data(
+        &self,
+        feed: B256,
+        max_data_age_ms: u64,
+    ) -> Result<StorageGuard<'_, PriceData>, RedStoneError> {
+        let data = self.get_feed_data(feed)?;
+        verify_data_age(
+            feed,
+            data.package_timestamp.get().saturating_to::<u64>(),
+            self.vm().block_timestamp() * 1000,
+            max_data_age_ms,
+        )?;
+
+        Ok(data)
+    }
+
+    fn write_feeds(
+        &mut self,
+        feeds: Vec<B256>,
+        payload: Bytes,
+        skip_failed: bool,
+    ) -> Result<Vec<B256>, RedStoneError> {
+        let block_timestamp_millis = self.vm().block_timestamp() * 1000;
+        let config = self.load_config();
+
+        let feed_values = self.process_feeds(
+            &config,
+            &feeds,
+            payload,
+            block_timestamp_millis,
+            skip_failed,
+        )?;
+
+        let verifier = UpdateTimestampVerifier::verifier(
+            &self.vm().msg_sender().into_array(),
+            config.trusted_updaters(),
+        );
+
+        let mut written = Vec::new();
+        let mut last_error = None;
+        for (feed, feed_value) in feeds.into_iter().zip(feed_values) {
+            let result = feed_value.and_then(|(new_price, timestamp)| {
+                let value = new_price.to_u256();
+                self.write_new_price(
+                    feed,
+                    new_price,
+                    timestamp,
+                    block_timestamp_millis,
+                    &verifier,
+                )?;
+
+                Ok((value, timestamp))
+            });
+
+            match result {
+                Ok((value, timestamp)) => {
+                    log(
+                        self.vm(),
+                        ValueUpdate {
+                            value,
+                            dataFeedId: feed,
+                            updatedAt: U256::from(timestamp.as_millis()),
+                        },
+                    );
+                    written.push(feed);
+                }
+                Err(error) if skip_failed => {
+                    log(
+                        self.vm(),
+                        UpdateSkipped {
+                            dataFeedId: feed,
+                            reason: Vec::<u8>::from(error.clone()).into(),
+                        },
+                    );
+                    last_error = Some(error);
+                }
+                Err(error) => return Err(error),
+            }
+        }
+
+        match last_error {
+            Some(error) if written.is_empty() => Err(error),
+            _ => Ok(written),
+        }
+    }
+
+    /// Returns the value and the package timestamp of each of the `feeds`.
+    ///
+    /// When the failed feeds are skipped, the ones contained in fewer data packages than
+    /// the signer count threshold fail with `NotEnoughResults` and the payload is processed
+    /// once for the other ones.
+    fn process_feeds(
+        &mut self,
+        config: &Config,
+        feeds: &[B256],
+        payload: Bytes,
+        block_timestamp_millis: u64,
+        skip_failed: bool,
+    ) -> Result<Vec<Result<(Value, TimestampMillis), RedStoneError>>, RedStoneError> {
+        let not_enough_results = || RedStoneError::NotEnoughResults(NotEnoughResults {});
+        let threshold = usize::from(config.signer_count_threshold);
+        let has_enough_packages: Vec<bool> = match count_feed_packages(&payload, feeds) {
+            Some(counts) if skip_failed => counts.iter().map(|&count| count >= threshold).collect(),
+            _ => vec![true; feeds.len()],
+        };
+        let processed_feeds: Vec<B256> = feeds
+            .iter()
+            .zip(&has_enough_packages)
+            .filter(|(_, &has_enough_packages)| has_enough_packages)
+            .map(|(&feed, _)| feed)
+            .collect();
+        if processed_feeds.is_empty() {
+            return Err(not_enough_results());
+        }
+
+        let processed_payload = Processor::process_payload(
+            self,
+            config,
+            &processed_feeds,
+            payload,
+            block_timestamp_millis,
+        )?;
+        if processed_payload.values.len() != processed_feeds.len() {
+            return Err(not_enough_results());
+        }
+
+        let timestamp = processed_payload.timestamp;
+        let mut values = processed_payload.values.into_iter();
+
+        Ok(has_enough_packages
+            .into_iter()
+            .map(|has_enough_packages| {
+                if !has_enough_packages {
+                    return Err(not_enough_results());
+                }
+
+                values
+                    .next()
+                    .map(|value| (value, timestamp))
+                    .ok_or_else(not_enough_results)
+            })
+            .collect())
+    }
+
+    fn verify_write_conditions(
+        &self,
+        feed: B256,
+        value: U256,
+        timestamp: u64,
+    ) -> Result<(), RedStoneError> {
+        let settings = self.feed_settings.get(feed);
+        settings.verify_data_age(feed, timestamp, self.vm().block_timestamp() * 1000)?;
+
+        let data = self.prices.get(feed);
+        if !settings.is_update_required(
+            data.value.get(),
+            data.package_timestamp.get().to::<u64>(),
+            value,
+            timestamp,
+        ) {
+            return Err(RedStoneError::UpdateNotRequired(UpdateNotRequired { feed }));
+        }
+
+        Ok(())
+    }
+
     fn write_new_price(
         &mut self,
         feed: B256,
@@ -197,6 +552,10 @@ impl PriceAdapter {
         block_timestamp_millis: u64,
         verifier: &UpdateTimestampVerifier,
     ) -> Result<(), RedStoneError> {
+        if matches!(verifier, UpdateTimestampVerifier::Untrusted) {
+            self.verify_write_conditions(feed, new_price.to_u256(), timestamp.as_millis())?;
+        }
+
         let mut feed_price = self.prices.setter(feed);
 
         let write_timestamp = feed_price.write_timestamp.get();
@@ -207,7 +566,7 @@ impl PriceAdapter {
         verifier.verify_timestamp(
             block_timestamp_millis.into(),
             write_time,
-            STYLUS_CONFIG.min_interval_between_updates_ms.into(),
+            MIN_INTERVAL_BETWEEN_UPDATES_MS.into(),
             package_timestamp,
             timestamp,
         )?;
@@ -224,3 +583,23 @@ impl PriceAdapter {
         Ok(())
     }
 }
+
+fn load_addresses(addresses: &StorageVec<StorageAddress>) -> Vec<Address> {
+    (0..addresses.len())
+        .filter_map(|index| addresses.get(index))
+        .collect()
+}
+
+fn load_signer_addresses(addresses: &StorageVec<StorageAddress>) -> Vec<SignerAddressBs> {
+    load_addresses(addresses)
+        .into_iter()
+        .map(Address::into_array)
+        .collect()
+}
+
+fn store_addresses(storage: &mut StorageVec<StorageAddress>, addresses: Vec<Address>) {
+    storage.erase();
+    for address in addresses {
+        storage.push(address);
+    }
+}
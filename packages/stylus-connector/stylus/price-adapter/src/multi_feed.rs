@@ -0,0 +1,88 @@
+use alloc::vec::Vec;
+
+use alloy_primitives::B256;
+use alloy_sol_types::{sol, SolCall};
+use stylus_sdk::abi::Bytes;
+
+use crate::error::{InvalidCalldata, RedStoneError};
+
+sol! {
+    function updateDataFeedsValuesPartial(bytes32[] dataFeedsIds);
+}
+
+/// Decodes the `updateDataFeedsValuesPartial` call of the EVM `MultiFeedAdapter`,
+/// which gets the RedStone payload appended to the ABI-encoded arguments.
+///
+/// Fails with `InvalidCalldata` for any other selector or malformed arguments.
+pub fn decode_update_data_feeds_values_partial(
+    calldata: &[u8],
+) -> Result<(Vec<B256>, Bytes), RedStoneError> {
+    let invalid_calldata = || RedStoneError::InvalidCalldata(InvalidCalldata {});
+
+    let arguments = calldata
+        .strip_prefix(&updateDataFeedsValuesPartialCall::SELECTOR)
+        .ok_or_else(invalid_calldata)?;
+    let call = updateDataFeedsValuesPartialCall::abi_decode_raw(arguments, false)
+        .map_err(|_| invalid_calldata())?;
+    let payload = arguments
+        .get(call.abi_encoded_size()..)
+        .ok_or_else(invalid_calldata)?
+        .to_vec();
+
+    Ok((call.dataFeedsIds, payload.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decode(calldata: &[u8]) -> Result<(Vec<B256>, Bytes), Vec<u8>> {
+        decode_update_data_feeds_values_partial(calldata).map_err(Vec::<u8>::from)
+    }
+
+    fn calldata(feed_ids: &[B256], payload: &[u8]) -> Vec<u8> {
+        let mut calldata = updateDataFeedsValuesPartialCall {
+            dataFeedsIds: feed_ids.to_vec(),
+        }
+        .abi_encode();
+        calldata.extend_from_slice(payload);
+
+        calldata
+    }
+
+    #[test]
+    fn decode_with_appended_payload() {
+        let feed_ids = [B256::repeat_byte(1), B256::repeat_byte(2)];
+        let payload = [7; 100];
+
+        let (decoded_feed_ids, decoded_payload) = decode(&calldata(&feed_ids, &payload)).unwrap();
+
+        assert_eq!(decoded_feed_ids, feed_ids);
+        assert_eq!(decoded_payload.as_slice(), payload);
+    }
+
+    #[test]
+    fn decode_without_payload() {
+        let feed_ids = [B256::repeat_byte(1)];
+
+        let (decoded_feed_ids, decoded_payload) = decode(&calldata(&feed_ids, &[])).unwrap();
+
+        assert_eq!(decoded_feed_ids, feed_ids);
+        assert!(decoded_payload.is_empty());
+    }
+
+    #[test]
+    fn decode_other_call() {
+        let mut calldata = calldata(&[B256::repeat_byte(1)], &[7; 10]);
+        calldata[0] ^= 1;
+
+        let invalid_calldata = Vec::<u8>::from(RedStoneError::InvalidCalldata(InvalidCalldata {}));
+
+        assert_eq!(decode(&calldata).unwrap_err(), invalid_calldata);
+        assert_eq!(decode(&calldata[..3]).unwrap_err(), invalid_calldata);
+        assert_eq!(
+            decode(&updateDataFeedsValuesPartialCall::SELECTOR).unwrap_err(),
+            invalid_calldata
+        );
+    }
+}
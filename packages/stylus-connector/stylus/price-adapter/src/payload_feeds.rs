@@ -0,0 +1,125 @@
+use alloc::vec::Vec;
+
+use alloy_primitives::B256;
+
+const REDSTONE_MARKER_BS: usize = 9;
+const UNSIGNED_METADATA_BYTE_SIZE_BS: usize = 3;
+const DATA_PACKAGES_COUNT_BS: usize = 2;
+const SIGNATURE_BS: usize = 65;
+const DATA_POINTS_COUNT_BS: usize = 3;
+const DATA_POINT_VALUE_BYTE_SIZE_BS: usize = 4;
+const TIMESTAMP_BS: usize = 6;
+const DATA_FEED_ID_BS: usize = 32;
+
+/// Counts the data packages of the RedStone `payload` containing each of the `feeds`.
+///
+/// Nothing is verified, so it only tells which feeds can't get enough signers
+/// before the payload is processed. Returns `None` for a malformed payload,
+/// left to be rejected by the processing.
+pub fn count_feed_packages(payload: &[u8], feeds: &[B256]) -> Option<Vec<usize>> {
+    let mut reader = Reader(payload);
+    reader.take(REDSTONE_MARKER_BS)?;
+    let unsigned_metadata_size = reader.take_number(UNSIGNED_METADATA_BYTE_SIZE_BS)?;
+    reader.take(unsigned_metadata_size)?;
+
+    let mut counts = vec![0; feeds.len()];
+    for _ in 0..reader.take_number(DATA_PACKAGES_COUNT_BS)? {
+        reader.take(SIGNATURE_BS)?;
+        let data_points_count = reader.take_number(DATA_POINTS_COUNT_BS)?;
+        let value_size = reader.take_number(DATA_POINT_VALUE_BYTE_SIZE_BS)?;
+        reader.take(TIMESTAMP_BS)?;
+
+        let data_point_size = DATA_FEED_ID_BS.checked_add(value_size)?;
+        let data_points = reader.take(data_points_count.checked_mul(data_point_size)?)?;
+        let package_feeds: Vec<&[u8]> = data_points
+            .chunks_exact(data_point_size)
+            .map(|data_point| &data_point[..DATA_FEED_ID_BS])
+            .collect();
+
+        for (count, feed) in counts.iter_mut().zip(feeds) {
+            if package_feeds.contains(&feed.as_slice()) {
+                *count += 1;
+            }
+        }
+    }
+
+    Some(counts)
+}
+
+/// Reads the payload from its end, as the RedStone protocol lays it out.
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Option<&'a [u8]> {
+        let (rest, taken) = self.0.split_at(self.0.len().checked_sub(len)?);
+        self.0 = rest;
+
+        Some(taken)
+    }
+
+    fn take_number(&mut self, len: usize) -> Option<usize> {
+        let number = self
+            .take(len)?
+            .iter()
+            .fold(0u64, |number, &byte| (number << 8) | u64::from(byte));
+
+        usize::try_from(number).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REDSTONE_MARKER: [u8; 9] = [0, 0, 2, 237, 87, 1, 30, 0, 0];
+
+    fn feed(feed_id: &str) -> B256 {
+        B256::right_padding_from(feed_id.as_bytes())
+    }
+
+    fn payload(packages: &[&[&str]], unsigned_metadata: &[u8]) -> Vec<u8> {
+        let mut payload = vec![];
+        for feed_ids in packages {
+            for feed_id in feed_ids.iter() {
+                payload.extend_from_slice(feed(feed_id).as_slice());
+                payload.extend_from_slice(&[1; 32]);
+            }
+            payload.extend_from_slice(&[0; TIMESTAMP_BS]);
+            payload.extend_from_slice(&32u32.to_be_bytes());
+            payload.extend_from_slice(&(feed_ids.len() as u32).to_be_bytes()[1..]);
+            payload.extend_from_slice(&[2; SIGNATURE_BS]);
+        }
+        payload.extend_from_slice(&(packages.len() as u16).to_be_bytes());
+        payload.extend_from_slice(unsigned_metadata);
+        payload.extend_from_slice(&(unsigned_metadata.len() as u32).to_be_bytes()[1..]);
+        payload.extend_from_slice(&REDSTONE_MARKER);
+
+        payload
+    }
+
+    #[test]
+    fn count_packages_of_feeds() {
+        let payload = payload(&[&["BTC", "ETH"], &["BTC"], &["ETH", "BTC"]], &[]);
+
+        assert_eq!(
+            count_feed_packages(&payload, &[feed("ETH"), feed("SOL"), feed("BTC")]),
+            Some(vec![2, 0, 3])
+        );
+    }
+
+    #[test]
+    fn count_packages_with_unsigned_metadata() {
+        let payload = payload(&[&["BTC"]], &[7; 10]);
+
+        assert_eq!(count_feed_packages(&payload, &[feed("BTC")]), Some(vec![1]));
+    }
+
+    #[test]
+    fn count_packages_of_malformed_payload() {
+        let payload = payload(&[&["BTC", "ETH"]], &[]);
+
+        assert_eq!(count_feed_packages(&payload[1..], &[feed("BTC")]), None);
+        assert_eq!(count_feed_packages(&payload[..8], &[feed("BTC")]), None);
+        assert_eq!(count_feed_packages(&[], &[feed("BTC")]), None);
+    }
+}
@@ -0,0 +1,463 @@
+use alloy_primitives::{address, keccak256};
+use alloy_sol_types::SolEvent;
+use openzeppelin_stylus::access::ownable::OwnableUnauthorizedAccount;
+use stylus_sdk::testing::*;
+
+use super::*;
+use crate::error::{InvalidCalldata, StaleData};
+
+const OWNER: Address = address!("00000000000000000000000000000000000000aa");
+const SIGNER: Address = address!("00000000000000000000000000000000000000bb");
+const UPDATER: Address = address!("00000000000000000000000000000000000000cc");
+const ECRECOVER: Address = address!("0000000000000000000000000000000000000001");
+const REDSTONE_MARKER: [u8; 9] = [0, 0, 2, 237, 87, 1, 30, 0, 0];
+const TIMESTAMP: u64 = 1_700_000_000_000;
+const SIGNATURE_R: [u8; 32] = [1; 32];
+const SIGNATURE_S: [u8; 32] = [2; 32];
+const RECOVERY_BYTE: u8 = 27;
+
+fn feed(feed_id: &str) -> B256 {
+    B256::right_padding_from(feed_id.as_bytes())
+}
+
+/// Builds a payload of a single package signed by `SIGNER`, mocking the `ecrecover` precompile
+/// to recover it from the package signature
+fn payload(vm: &TestVM, values: &[(&str, u64)], timestamp: u64) -> Bytes {
+    let mut package = vec![];
+    for &(feed_id, value) in values {
+        package.extend_from_slice(feed(feed_id).as_slice());
+        package.extend_from_slice(&U256::from(value).to_be_bytes::<32>());
+    }
+    package.extend_from_slice(&timestamp.to_be_bytes()[2..]);
+    package.extend_from_slice(&32u32.to_be_bytes());
+    package.extend_from_slice(&(values.len() as u32).to_be_bytes()[1..]);
+
+    let mut ecrecover_calldata = keccak256(&package).to_vec();
+    ecrecover_calldata.extend_from_slice(&U256::from(RECOVERY_BYTE).to_be_bytes::<32>());
+    ecrecover_calldata.extend_from_slice(&SIGNATURE_R);
+    ecrecover_calldata.extend_from_slice(&SIGNATURE_S);
+    vm.mock_static_call(
+        ECRECOVER,
+        ecrecover_calldata,
+        Ok(SIGNER.into_word().to_vec()),
+    );
+
+    package.extend_from_slice(&SIGNATURE_R);
+    package.extend_from_slice(&SIGNATURE_S);
+    package.push(RECOVERY_BYTE);
+
+    package.extend_from_slice(&1u16.to_be_bytes());
+    // no unsigned metadata
+    package.extend_from_slice(&[0; 3]);
+    package.extend_from_slice(&REDSTONE_MARKER);
+
+    package.into()
+}
+
+fn adapter(vm: &TestVM) -> PriceAdapter {
+    let mut adapter = PriceAdapter::from(vm);
+    vm.set_sender(OWNER);
+    vm.set_block_timestamp(TIMESTAMP / 1000);
+    encoded(adapter.constructor(OWNER, vec![SIGNER], 1, vec![])).unwrap();
+    vm.set_sender(UPDATER);
+
+    adapter
+}
+
+fn encoded<T>(result: Result<T, RedStoneError>) -> Result<T, Vec<u8>> {
+    result.map_err(Vec::<u8>::from)
+}
+
+fn events<E: SolEvent>(vm: &TestVM) -> Vec<E> {
+    vm.get_emitted_logs()
+        .into_iter()
+        .filter(|(topics, _)| topics.first() == Some(&E::SIGNATURE_HASH))
+        .map(|(topics, data)| E::decode_raw_log(topics, &data, true).unwrap())
+        .collect()
+}
+
+#[test]
+fn write_prices_partial_skips_feeds_missing_in_payload() {
+    let vm = TestVM::default();
+    let mut adapter = adapter(&vm);
+    let payload = payload(&vm, &[("BTC", 100)], TIMESTAMP);
+
+    let written = encoded(adapter.write_prices_partial(vec![feed("BTC"), feed("ETH")], payload));
+
+    assert_eq!(written.unwrap(), vec![feed("BTC")]);
+    assert_eq!(adapter.read_price(feed("BTC")), U256::from(100));
+    assert_eq!(adapter.read_price(feed("ETH")), U256::ZERO);
+
+    let updates = events::<ValueUpdate>(&vm);
+    assert_eq!(updates.len(), 1);
+    assert_eq!(updates[0].dataFeedId, feed("BTC"));
+    assert_eq!(updates[0].value, U256::from(100));
+    assert_eq!(updates[0].updatedAt, U256::from(TIMESTAMP));
+
+    let skipped = events::<UpdateSkipped>(&vm);
+    assert_eq!(skipped.len(), 1);
+    assert_eq!(skipped[0].dataFeedId, feed("ETH"));
+    assert_eq!(
+        skipped[0].reason.to_vec(),
+        Vec::<u8>::from(RedStoneError::NotEnoughResults(NotEnoughResults {}))
+    );
+}
+
+#[test]
+fn write_prices_partial_returns_written_feeds() {
+    let vm = TestVM::default();
+    let mut adapter = adapter(&vm);
+    let payload = payload(&vm, &[("BTC", 100), ("ETH", 200)], TIMESTAMP);
+
+    let written = encoded(adapter.write_prices_partial(vec![feed("ETH"), feed("BTC")], payload));
+
+    assert_eq!(written.unwrap(), vec![feed("ETH"), feed("BTC")]);
+    assert_eq!(adapter.read_price(feed("BTC")), U256::from(100));
+    assert_eq!(adapter.read_price(feed("ETH")), U256::from(200));
+    assert_eq!(events::<ValueUpdate>(&vm).len(), 2);
+    assert!(events::<UpdateSkipped>(&vm).is_empty());
+}
+
+#[test]
+fn write_prices_partial_reverts_when_no_feed_written() {
+    let vm = TestVM::default();
+    let mut adapter = adapter(&vm);
+    let payload = payload(&vm, &[("BTC", 100)], TIMESTAMP);
+
+    assert_eq!(
+        encoded(adapter.write_prices_partial(vec![feed("ETH")], payload.clone())).unwrap_err(),
+        Vec::<u8>::from(RedStoneError::NotEnoughResults(NotEnoughResults {}))
+    );
+    assert_eq!(adapter.read_price(feed("ETH")), U256::ZERO);
+
+    encoded(adapter.write_prices_partial(vec![feed("BTC")], payload.clone())).unwrap();
+
+    // the same package can't be written again
+    assert!(adapter
+        .write_prices_partial(vec![feed("BTC")], payload)
+        .is_err());
+    assert_eq!(adapter.read_price(feed("BTC")), U256::from(100));
+}
+
+#[test]
+fn write_prices_fails_on_feed_missing_in_payload() {
+    let vm = TestVM::default();
+    let mut adapter = adapter(&vm);
+    let payload = payload(&vm, &[("BTC", 100)], TIMESTAMP);
+
+    assert!(adapter
+        .write_prices(vec![feed("BTC"), feed("ETH")], payload)
+        .is_err());
+    assert_eq!(adapter.read_price(feed("BTC")), U256::ZERO);
+}
+
+#[test]
+fn update_required_without_settings_or_previous_value() {
+    let vm = TestVM::default();
+    let mut adapter = adapter(&vm);
+    let value = U256::from(100);
+
+    let settings = adapter.feed_settings.setter(feed("BTC"));
+    assert!(settings.is_update_required(value, TIMESTAMP, value, TIMESTAMP + 1));
+
+    let mut settings = adapter.feed_settings.setter(feed("ETH"));
+    settings.set(60_000, 100, 0);
+    assert!(settings.is_update_required(U256::ZERO, 0, value, TIMESTAMP));
+}
+
+#[test]
+fn update_required_after_heartbeat() {
+    let vm = TestVM::default();
+    let mut adapter = adapter(&vm);
+    let value = U256::from(100);
+
+    let mut settings = adapter.feed_settings.setter(feed("BTC"));
+    settings.set(60_000, 0, 0);
+
+    assert!(!settings.is_update_required(value, TIMESTAMP, value, TIMESTAMP + 59_999));
+    assert!(settings.is_update_required(value, TIMESTAMP, value, TIMESTAMP + 60_000));
+    assert!(!settings.is_update_required(value, TIMESTAMP, U256::from(200), TIMESTAMP + 1));
+}
+
+#[test]
+fn update_required_on_deviation() {
+    let vm = TestVM::default();
+    let mut adapter = adapter(&vm);
+    let current_value = U256::from(10_000);
+
+    let mut settings = adapter.feed_settings.setter(feed("BTC"));
+    settings.set(0, 100, 0);
+
+    for (value, required) in [
+        (10_099, false),
+        (10_100, true),
+        (9_901, false),
+        (9_900, true),
+    ] {
+        assert_eq!(
+            settings.is_update_required(current_value, TIMESTAMP, U256::from(value), TIMESTAMP),
+            required
+        );
+    }
+
+    assert!(settings.is_update_required(U256::ZERO, TIMESTAMP, U256::ZERO, TIMESTAMP));
+    assert!(settings.is_update_required(U256::ZERO, TIMESTAMP, current_value, TIMESTAMP));
+}
+
+#[test]
+fn update_required_with_heartbeat_and_deviation() {
+    let vm = TestVM::default();
+    let mut adapter = adapter(&vm);
+    let current_value = U256::from(10_000);
+
+    let mut settings = adapter.feed_settings.setter(feed("BTC"));
+    settings.set(60_000, 100, 0);
+
+    assert!(!settings.is_update_required(current_value, TIMESTAMP, current_value, TIMESTAMP + 1));
+    assert!(settings.is_update_required(
+        current_value,
+        TIMESTAMP,
+        U256::from(10_100),
+        TIMESTAMP + 1
+    ));
+    assert!(settings.is_update_required(
+        current_value,
+        TIMESTAMP,
+        current_value,
+        TIMESTAMP + 60_000
+    ));
+}
+
+#[test]
+fn update_required_with_max_data_age_only() {
+    let vm = TestVM::default();
+    let mut adapter = adapter(&vm);
+    let value = U256::from(100);
+
+    let mut settings = adapter.feed_settings.setter(feed("BTC"));
+    settings.set(0, 0, 60_000);
+
+    assert!(settings.is_update_required(value, TIMESTAMP, value, TIMESTAMP + 1));
+    assert!(settings
+        .verify_data_age(feed("BTC"), TIMESTAMP - 60_000, TIMESTAMP)
+        .is_ok());
+    assert_eq!(
+        encoded(settings.verify_data_age(feed("BTC"), TIMESTAMP - 60_001, TIMESTAMP)).unwrap_err(),
+        Vec::<u8>::from(RedStoneError::StaleData(StaleData {
+            feed: feed("BTC"),
+            age: U256::from(60_001),
+        }))
+    );
+
+    settings.set(0, 0, 0);
+    assert!(settings.verify_data_age(feed("BTC"), 0, TIMESTAMP).is_ok());
+}
+
+#[test]
+fn write_prices_rejects_update_not_required() {
+    let vm = TestVM::default();
+    let mut adapter = adapter(&vm);
+
+    vm.set_sender(OWNER);
+    encoded(adapter.set_feed_settings(feed("BTC"), 60 * 60 * 1000, 100, 0)).unwrap();
+    encoded(adapter.set_trusted_updaters(vec![OWNER])).unwrap();
+    vm.set_sender(UPDATER);
+
+    let first_payload = payload(&vm, &[("BTC", 10_000)], TIMESTAMP);
+    encoded(adapter.write_prices(vec![feed("BTC")], first_payload)).unwrap();
+
+    vm.set_block_timestamp(TIMESTAMP / 1000 + 60);
+    let next_payload = payload(&vm, &[("BTC", 10_099)], TIMESTAMP + 60_000);
+
+    assert_eq!(
+        encoded(adapter.write_prices(vec![feed("BTC")], next_payload.clone())).unwrap_err(),
+        Vec::<u8>::from(RedStoneError::UpdateNotRequired(UpdateNotRequired {
+            feed: feed("BTC")
+        }))
+    );
+    assert_eq!(adapter.read_price(feed("BTC")), U256::from(10_000));
+
+    // the conditions aren't checked for the trusted updaters
+    vm.set_sender(OWNER);
+    encoded(adapter.write_prices(vec![feed("BTC")], next_payload)).unwrap();
+    assert_eq!(adapter.read_price(feed("BTC")), U256::from(10_099));
+}
+
+#[test]
+fn verify_data_age_of_package() {
+    assert!(verify_data_age(feed("BTC"), TIMESTAMP - 60_000, TIMESTAMP, 60_000).is_ok());
+    assert!(verify_data_age(feed("BTC"), TIMESTAMP + 60_000, TIMESTAMP, 0).is_ok());
+    assert_eq!(
+        encoded(verify_data_age(
+            feed("BTC"),
+            TIMESTAMP - 60_001,
+            TIMESTAMP,
+            60_000
+        ))
+        .unwrap_err(),
+        Vec::<u8>::from(RedStoneError::StaleData(StaleData {
+            feed: feed("BTC"),
+            age: U256::from(60_001),
+        }))
+    );
+}
+
+#[test]
+fn latest_round_data_of_unknown_feed() {
+    let vm = TestVM::default();
+    let adapter = adapter(&vm);
+
+    assert_eq!(
+        encoded(adapter.latest_round_data(feed("BTC"))).unwrap_err(),
+        Vec::<u8>::from(RedStoneError::from(feed("BTC")))
+    );
+}
+
+#[test]
+fn latest_round_data_of_fresh_feed() {
+    let vm = TestVM::default();
+    let mut adapter = adapter(&vm);
+    let payload = payload(&vm, &[("BTC", 100)], TIMESTAMP);
+    encoded(adapter.write_prices(vec![feed("BTC")], payload)).unwrap();
+
+    vm.set_block_timestamp((TIMESTAMP + DEFAULT_MAX_DATA_AGE_MS) / 1000);
+    let (round_id, answer, started_at, updated_at, answered_in_round) =
+        encoded(adapter.latest_round_data(feed("BTC"))).unwrap();
+
+    assert_eq!(round_id, U80::from(LATEST_ROUND_ID));
+    assert_eq!(answered_in_round, round_id);
+    assert_eq!(answer, I256::try_from(100).unwrap());
+    assert_eq!(updated_at, U256::from(TIMESTAMP / 1000));
+    assert_eq!(
+        updated_at,
+        encoded(adapter.get_block_timestamp_from_latest_update(feed("BTC"))).unwrap()
+    );
+    assert_eq!(started_at, updated_at);
+}
+
+#[test]
+fn latest_round_data_of_stale_feed() {
+    let vm = TestVM::default();
+    let mut adapter = adapter(&vm);
+    let payload = payload(&vm, &[("BTC", 100), ("ETH", 200)], TIMESTAMP);
+    encoded(adapter.write_prices(vec![feed("BTC"), feed("ETH")], payload)).unwrap();
+
+    vm.set_sender(OWNER);
+    encoded(adapter.set_feed_settings(feed("ETH"), 0, 0, 60_000)).unwrap();
+
+    vm.set_block_timestamp(TIMESTAMP / 1000 + 61);
+    assert!(adapter.latest_round_data(feed("BTC")).is_ok());
+    assert_eq!(
+        encoded(adapter.latest_round_data(feed("ETH"))).unwrap_err(),
+        Vec::<u8>::from(RedStoneError::StaleData(StaleData {
+            feed: feed("ETH"),
+            age: U256::from(61_000),
+        }))
+    );
+
+    vm.set_block_timestamp((TIMESTAMP + DEFAULT_MAX_DATA_AGE_MS) / 1000 + 1);
+    assert_eq!(
+        encoded(adapter.latest_round_data(feed("BTC"))).unwrap_err(),
+        Vec::<u8>::from(RedStoneError::StaleData(StaleData {
+            feed: feed("BTC"),
+            age: U256::from(DEFAULT_MAX_DATA_AGE_MS + 1_000),
+        }))
+    );
+}
+
+#[test]
+fn value_with_max_age() {
+    let vm = TestVM::default();
+    let mut adapter = adapter(&vm);
+    let payload = payload(&vm, &[("BTC", 100)], TIMESTAMP);
+    encoded(adapter.write_prices(vec![feed("BTC")], payload)).unwrap();
+
+    vm.set_block_timestamp(TIMESTAMP / 1000 + 60);
+    let max_age_ms = U256::from(60_000);
+
+    assert_eq!(
+        encoded(adapter.get_value_for_data_feed_with_max_age(feed("BTC"), max_age_ms)).unwrap(),
+        U256::from(100)
+    );
+    assert!(adapter
+        .get_value_for_data_feed_with_max_age(feed("BTC"), max_age_ms - U256::from(1))
+        .is_err());
+    assert!(adapter
+        .get_value_for_data_feed_with_max_age(feed("ETH"), max_age_ms)
+        .is_err());
+}
+
+#[test]
+fn two_step_ownership_transfer() {
+    let vm = TestVM::default();
+    let mut adapter = adapter(&vm);
+    let unauthorized = Vec::<u8>::from(RedStoneError::UnauthorizedAccount(
+        OwnableUnauthorizedAccount { account: UPDATER },
+    ));
+
+    assert_eq!(
+        encoded(adapter.set_trusted_updaters(vec![UPDATER])).unwrap_err(),
+        unauthorized
+    );
+    assert_eq!(
+        encoded(adapter.transfer_ownership(UPDATER)).unwrap_err(),
+        unauthorized
+    );
+
+    vm.set_sender(OWNER);
+    encoded(adapter.transfer_ownership(UPDATER)).unwrap();
+    assert_eq!(adapter.owner(), OWNER);
+    assert_eq!(adapter.pending_owner(), UPDATER);
+
+    vm.set_sender(UPDATER);
+    encoded(adapter.accept_ownership()).unwrap();
+    assert_eq!(adapter.owner(), UPDATER);
+    assert_eq!(adapter.pending_owner(), Address::ZERO);
+    encoded(adapter.set_trusted_updaters(vec![UPDATER])).unwrap();
+}
+
+#[test]
+fn fallback_with_unknown_selector() {
+    let vm = TestVM::default();
+    let mut adapter = adapter(&vm);
+
+    assert_eq!(
+        adapter.fallback(&[0xde, 0xad, 0xbe, 0xef]).unwrap_err(),
+        Vec::<u8>::from(RedStoneError::InvalidCalldata(InvalidCalldata {}))
+    );
+}
+
+#[test]
+fn write_timestamp_in_millis() {
+    let vm = TestVM::default();
+    let mut adapter = adapter(&vm);
+    let first_payload = payload(&vm, &[("BTC", 100)], TIMESTAMP);
+    encoded(adapter.write_prices(vec![feed("BTC")], first_payload)).unwrap();
+
+    assert_eq!(
+        adapter.prices.get(feed("BTC")).write_timestamp.get(),
+        U256::from(TIMESTAMP)
+    );
+    assert_eq!(
+        adapter.get_last_update_details_unsafe(feed("BTC")),
+        (
+            U256::from(TIMESTAMP),
+            U256::from(TIMESTAMP / 1000),
+            U256::from(100)
+        )
+    );
+
+    vm.set_block_timestamp((TIMESTAMP + MIN_INTERVAL_BETWEEN_UPDATES_MS) / 1000 - 1);
+    // the min interval between the untrusted updates is checked against the write in ms
+    let payload = payload(&vm, &[("BTC", 200)], TIMESTAMP + 30_000);
+    assert!(adapter
+        .write_prices_partial(vec![feed("BTC")], payload.clone())
+        .is_err());
+
+    vm.set_block_timestamp((TIMESTAMP + MIN_INTERVAL_BETWEEN_UPDATES_MS) / 1000);
+    encoded(adapter.write_prices_partial(vec![feed("BTC")], payload)).unwrap();
+    assert_eq!(
+        adapter.prices.get(feed("BTC")).write_timestamp.get(),
+        U256::from(TIMESTAMP + MIN_INTERVAL_BETWEEN_UPDATES_MS)
+    );
+}
@@ -0,0 +1,19 @@
+use alloy_primitives::aliases::U80;
+use stylus_sdk::{alloy_sol_types::sol, prelude::SolidityError};
+
+sol! {
+    error GetRoundDataCanBeOnlyCalledWithLatestRound(uint80 requested_round_id);
+}
+
+#[derive(SolidityError)]
+pub enum PriceFeedError {
+    GetRoundDataCanBeOnlyCalledWithLatestRound(GetRoundDataCanBeOnlyCalledWithLatestRound),
+}
+
+impl PriceFeedError {
+    pub fn not_latest_round(requested_round_id: U80) -> Self {
+        PriceFeedError::GetRoundDataCanBeOnlyCalledWithLatestRound(
+            GetRoundDataCanBeOnlyCalledWithLatestRound { requested_round_id },
+        )
+    }
+}
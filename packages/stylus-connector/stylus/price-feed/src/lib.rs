@@ -0,0 +1,213 @@
+//! Chainlink `AggregatorV3Interface` of a single data feed of the `PriceAdapter`,
+//! with the same ABI as the EVM `PriceFeedWithoutRounds`.
+//!
+//! The adapter errors, e.g. `DataFeedNotFound`, are passed through to the caller.
+#![cfg_attr(not(any(test, feature = "export-abi")), no_main)]
+extern crate alloc;
+
+use alloc::{string::String, vec::Vec};
+
+use crate::error::PriceFeedError;
+use alloy_primitives::{aliases::U80, Address, B256, I256, U256, U8};
+use stylus_sdk::{
+    call::Call,
+    prelude::*,
+    storage::{StorageAddress, StorageB256, StorageString, StorageU8},
+};
+
+mod error;
+
+/// The feed has no rounds, so the latest one is always reported with the same id.
+const DEFAULT_ROUND_ID: u64 = 1;
+const VERSION: u64 = 1;
+
+sol_interface! {
+    interface IPriceAdapter {
+        function latestRoundData(bytes32 feed) external view returns (uint80, int256, uint256, uint256, uint80);
+    }
+}
+
+type RoundData = (U80, I256, U256, U256, U80);
+
+#[entrypoint]
+#[storage]
+struct PriceFeed {
+    adapter: StorageAddress,
+    data_feed_id: StorageB256,
+    decimals: StorageU8,
+    description: StorageString,
+}
+
+#[public]
+impl PriceFeed {
+    #[constructor]
+    pub fn constructor(
+        &mut self,
+        adapter: Address,
+        data_feed_id: B256,
+        decimals: u8,
+        description: String,
+    ) {
+        self.adapter.set(adapter);
+        self.data_feed_id.set(data_feed_id);
+        self.decimals.set(U8::from(decimals));
+        self.description.set_str(description);
+    }
+
+    pub fn get_price_feed_adapter(&self) -> Address {
+        self.adapter.get()
+    }
+
+    pub fn get_data_feed_id(&self) -> B256 {
+        self.data_feed_id.get()
+    }
+
+    pub fn decimals(&self) -> u8 {
+        self.decimals.get().to::<u8>()
+    }
+
+    pub fn description(&self) -> String {
+        self.description.get_string()
+    }
+
+    pub fn version(&self) -> U256 {
+        U256::from(VERSION)
+    }
+
+    pub fn latest_round(&self) -> U80 {
+        U80::from(DEFAULT_ROUND_ID)
+    }
+
+    /// The answer of `latest_round_data`, so it fails on the stale data the same way
+    pub fn latest_answer(&self) -> Result<I256, Vec<u8>> {
+        let (_, answer, ..) = self.latest_round_data()?;
+
+        Ok(answer)
+    }
+
+    /// Returns (roundId, answer, startedAt, updatedAt, answeredInRound)
+    /// with the block timestamp of the latest update as both `startedAt` and `updatedAt`.
+    /// Fails with the `StaleData` of the adapter when the value is older than the max data age of the feed,
+    /// or with its `UnsafeUintToIntConversion` when the value doesn't fit in `int256`
+    pub fn latest_round_data(&self) -> Result<RoundData, Vec<u8>> {
+        let adapter = IPriceAdapter::new(self.adapter.get());
+
+        Ok(adapter.latest_round_data(self.vm(), Call::new(), self.data_feed_id.get())?)
+    }
+
+    /// Only the latest round is available
+    pub fn get_round_data(&self, round_id: U80) -> Result<RoundData, Vec<u8>> {
+        if round_id != self.latest_round() {
+            return Err(PriceFeedError::not_latest_round(round_id).into());
+        }
+
+        self.latest_round_data()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::address;
+    use stylus_sdk::{
+        alloy_sol_types::{sol, SolCall, SolError},
+        testing::*,
+    };
+
+    use super::*;
+
+    sol! {
+        function latestRoundData(bytes32 feed) external view returns (uint80, int256, uint256, uint256, uint80);
+        error DataFeedNotFound(bytes32 feed);
+        error StaleData(bytes32 feed, uint256 age);
+    }
+
+    const ADAPTER: Address = address!("00000000000000000000000000000000000000aa");
+
+    fn feed() -> B256 {
+        B256::right_padding_from(b"BTC")
+    }
+
+    fn price_feed(vm: &TestVM) -> PriceFeed {
+        let mut price_feed = PriceFeed::from(vm);
+        price_feed.constructor(ADAPTER, feed(), 8, "BTC / USD".into());
+
+        price_feed
+    }
+
+    fn mock_latest_round_data(vm: &TestVM, result: Result<Vec<u8>, Vec<u8>>) {
+        vm.mock_static_call(
+            ADAPTER,
+            latestRoundDataCall { feed: feed() }.abi_encode(),
+            result,
+        );
+    }
+
+    #[test]
+    fn latest_round_data_of_adapter() {
+        let vm = TestVM::default();
+        let price_feed = price_feed(&vm);
+        let round_id = U80::from(DEFAULT_ROUND_ID);
+        let updated_at = U256::from(1_700_000_000);
+        let round_data = (
+            round_id,
+            I256::try_from(100).unwrap(),
+            updated_at,
+            updated_at,
+            round_id,
+        );
+        mock_latest_round_data(
+            &vm,
+            Ok(latestRoundDataCall::abi_encode_returns(&round_data)),
+        );
+
+        assert_eq!(price_feed.latest_round_data().unwrap(), round_data);
+        assert_eq!(price_feed.latest_answer().unwrap(), round_data.1);
+        assert_eq!(price_feed.get_round_data(round_id).unwrap(), round_data);
+    }
+
+    #[test]
+    fn latest_round_data_of_unknown_feed() {
+        let vm = TestVM::default();
+        let price_feed = price_feed(&vm);
+        let error = DataFeedNotFound { feed: feed() }.abi_encode();
+        mock_latest_round_data(&vm, Err(error.clone()));
+
+        assert_eq!(price_feed.latest_round_data().unwrap_err(), error);
+    }
+
+    #[test]
+    fn latest_round_data_of_stale_feed() {
+        let vm = TestVM::default();
+        let price_feed = price_feed(&vm);
+        let error = StaleData {
+            feed: feed(),
+            age: U256::from(24 * 60 * 60 * 1_000 + 1),
+        }
+        .abi_encode();
+        mock_latest_round_data(&vm, Err(error.clone()));
+
+        assert_eq!(price_feed.latest_round_data().unwrap_err(), error);
+        assert_eq!(price_feed.latest_answer().unwrap_err(), error);
+        assert_eq!(
+            price_feed
+                .get_round_data(U80::from(DEFAULT_ROUND_ID))
+                .unwrap_err(),
+            error
+        );
+    }
+
+    #[test]
+    fn get_round_data_of_not_latest_round() {
+        let vm = TestVM::default();
+        let price_feed = PriceFeed::from(&vm);
+
+        for round_id in [0, DEFAULT_ROUND_ID + 1] {
+            let round_id = U80::from(round_id);
+
+            assert_eq!(
+                price_feed.get_round_data(round_id).unwrap_err(),
+                Vec::<u8>::from(PriceFeedError::not_latest_round(round_id))
+            );
+        }
+    }
+}
@@ -1,9 +1,10 @@
 use crate::crypto::AdapterWrapper;
 use alloy_primitives::hex;
 use redstone::{
+    contract::verification::verify_signers_config,
     core::config::Config as RedstoneConfig,
     network::{error::Error, StdEnv},
-    FeedId, RedStoneConfigImpl, TimestampMillis,
+    FeedId, RedStoneConfigImpl, SignerAddress, TimestampMillis,
 };
 
 pub type SignerAddressBs = [u8; 20];
@@ -18,11 +19,11 @@ pub const REDSTONE_PRIMARY_PROD_ALLOWED_SIGNERS: [SignerAddressBs; 5] = [
     hex!("9c5ae89c4af6aa32ce58588dbaf90d18a855b6de"),
 ];
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Config {
     pub signer_count_threshold: u8,
-    pub signers: [SignerAddressBs; 5],
-    pub trusted_updaters: [SignerAddressBs; 1],
+    pub signers: Vec<SignerAddressBs>,
+    pub trusted_updaters: Vec<SignerAddressBs>,
     pub max_timestamp_delay_ms: u64,
     pub max_timestamp_ahead_ms: u64,
     pub min_interval_between_updates_ms: u64,
@@ -46,6 +47,13 @@ impl Config {
         Ok((config, price_adapter).into())
     }
 
+    /// Checks the signers are unique and there are at least `signer_count_threshold` of them.
+    pub fn verify(&self) -> Result<(), Error> {
+        let signers: Vec<SignerAddress> = self.signers.iter().map(|s| s.to_vec().into()).collect();
+
+        verify_signers_config(&signers, self.signer_count_threshold)
+    }
+
     pub fn trusted_updaters(&self) -> &[SignerAddressBs] {
         &self.trusted_updaters
     }
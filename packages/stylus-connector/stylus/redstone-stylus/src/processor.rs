@@ -11,7 +11,7 @@ pub struct Processor {}
 impl Processor {
     pub fn process_payload<Adapter: TopLevelStorage>(
         adapter: &mut Adapter,
-        stylus_config: Config,
+        stylus_config: &Config,
         feeds: &[B256],
         payload: Bytes,
         block_timestamp_millis: u64,